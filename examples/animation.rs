@@ -3,6 +3,7 @@ use tge::math::{Size, Region};
 use tge::engine::{Engine, EngineBuilder};
 use tge::window::WindowConfig;
 use tge::graphics::*;
+use tge::timer::TaskHandle;
 use tge::game::Game;
 use std::time::Duration;
 
@@ -10,11 +11,10 @@ const TITLE: &str = "Animation";
 
 struct App {
     texture: Texture,
-    animation_fps: f32,
     total_frame: usize,
     frame_size: Size,
     current_frame: usize,
-    current_frame_time: Duration,
+    frame_task: TaskHandle,
 }
 
 impl App {
@@ -24,13 +24,14 @@ impl App {
         let texture_size = texture.size();
         let total_frame = 8;
         let frame_size = Size::new(texture_size.width as f32 / total_frame as f32, texture_size.height as f32);
+        let animation_fps = 10.0;
+        let frame_task = engine.timer().scheduler().every(Duration::from_secs_f32(1.0 / animation_fps));
         Ok(Self {
             texture,
-            animation_fps: 10.0,
             total_frame,
             frame_size,
             current_frame: 0,
-            current_frame_time: Duration::new(0, 0),
+            frame_task,
         })
     }
 
@@ -39,17 +40,11 @@ impl App {
 impl Game for App {
 
     fn update(&mut self, engine: &mut Engine) -> GameResult {
-        let title = format!("{} - FPS: {}", TITLE, engine.timer().real_time_fps().round());
+        let title = format!("{} - FPS: {}", TITLE, engine.timer().stats().average_fps().round());
         engine.window().set_title(title);
 
-        self.current_frame_time += engine.timer().delta_time();
-        if self.current_frame_time.as_secs_f32() >= 1.0 / self.animation_fps {
-            self.current_frame_time = Duration::new(0, 0);
-            self.current_frame += 1;
-            if self.current_frame >= self.total_frame {
-                self.current_frame = 0;
-            }
-        }
+        let elapsed_frames = engine.timer().scheduler().poll(self.frame_task) as usize;
+        self.current_frame = (self.current_frame + elapsed_frames) % self.total_frame;
 
         Ok(())
     }
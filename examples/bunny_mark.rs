@@ -59,7 +59,7 @@ impl App {
 impl Game for App {
 
     fn update(&mut self, engine: &mut Engine) -> GameResult {
-        let title = format!("{}: {} - FPS: {}", TITLE, self.bunnies.len(), engine.timer().real_time_fps().round());
+        let title = format!("{}: {} - FPS: {}", TITLE, self.bunnies.len(), engine.timer().stats().average_fps().round());
         engine.window().set_title(title);
 
         if engine.mouse().is_button_down(MouseButton::Left) {
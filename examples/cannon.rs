@@ -66,7 +66,7 @@ impl App {
 impl Game for App {
 
     fn update(&mut self, engine: &mut Engine) -> GameResult {
-        let title = format!("{} - FPS: {}", TITLE, engine.timer().real_time_fps().round());
+        let title = format!("{} - FPS: {}", TITLE, engine.timer().stats().average_fps().round());
         engine.window().set_title(title);
 
         if let Some(mouse_position) = engine.mouse().position() {
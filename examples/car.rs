@@ -4,6 +4,7 @@ use tge::engine::{Engine, EngineBuilder};
 use tge::window::WindowConfig;
 use tge::graphics::*;
 use tge::keyboard::KeyCode;
+use tge::filesystem::FilesystemConfig;
 use tge::game::Game;
 
 const TITLE: &str = "Car";
@@ -39,7 +40,7 @@ impl App {
 impl Game for App {
 
     fn update(&mut self, engine: &mut Engine) -> GameResult {
-        let title = format!("{} - FPS: {}", TITLE, engine.timer().real_time_fps().round());
+        let title = format!("{} - FPS: {}", TITLE, engine.timer().stats().average_fps().round());
         engine.window().set_title(title);
 
         if engine.keyboard().is_key_hold(KeyCode::Up) || engine.keyboard().is_key_hold(KeyCode::W) {
@@ -98,6 +99,11 @@ impl Game for App {
                 .scale((0.16, 0.16)),
         );
 
+        if engine.keyboard().is_key_down(KeyCode::F12) {
+            let screenshot = engine.graphics().screenshot()?;
+            screenshot.save(engine, "screenshot.png")?;
+        }
+
         Ok(())
     }
 
@@ -105,6 +111,8 @@ impl Game for App {
 
 fn main() -> GameResult {
     EngineBuilder::new()
+        .filesystem_config(FilesystemConfig::new()
+            .identity("tge-car"))
         .window_config(WindowConfig::new()
             .title(TITLE)
             .inner_size((1280.0, 720.0)))
@@ -32,7 +32,7 @@ impl Game for App {
     fn update(&mut self, engine: &mut Engine) -> GameResult {
         let now_time = Local::now().time();
 
-        let title = format!("{} - FPS: {}", now_time.format("%H:%M:%S"), engine.timer().real_time_fps().round());
+        let title = format!("{} - FPS: {}", now_time.format("%H:%M:%S"), engine.timer().stats().average_fps().round());
         engine.window().set_title(title);
 
         self.second_angle = Angle::degrees(now_time.second() as f32 * 6.0);
@@ -49,12 +49,14 @@ impl Game for App {
             Some(&self.clock_disk),
             SpriteDrawParams::default(),
         );
+
+        engine.graphics().push_transform();
+        engine.graphics().translate((300.0, 300.0));
         engine.graphics().draw_sprite(
             NO_TEXTURE,
             SpriteDrawParams::default()
                 .region((0.0, 0.0, 30.0, 200.0))
                 .origin((15.0, 190.0))
-                .position((300.0, 300.0))
                 .rotation(self.hour_angle)
                 .color((0.2, 0.2, 1.0, 1.0)),
         );
@@ -63,7 +65,6 @@ impl Game for App {
             SpriteDrawParams::default()
                 .region((0.0, 0.0, 20.0, 240.0))
                 .origin((10.0, 220.0))
-                .position((300.0, 300.0))
                 .rotation(self.minute_angle)
                 .color((0.2, 1.0, 0.2, 1.0)),
         );
@@ -72,7 +73,6 @@ impl Game for App {
             SpriteDrawParams::default()
                 .region((0.0, 0.0, 10.0, 270.0))
                 .origin((5.0, 240.0))
-                .position((300.0, 300.0))
                 .rotation(self.second_angle)
                 .color((1.0, 0.2, 0.2, 1.0)),
         );
@@ -81,9 +81,9 @@ impl Game for App {
             SpriteDrawParams::default()
                 .region((0.0, 0.0, 6.0, 6.0))
                 .origin((3.0, 3.0))
-                .position((300.0, 300.0))
                 .color(Color::BLACK),
         );
+        engine.graphics().pop_transform();
 
         Ok(())
     }
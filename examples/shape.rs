@@ -1,5 +1,5 @@
 use tge::error::GameResult;
-use tge::math::{Vector, Position};
+use tge::math::{Vector, Position, Angle};
 use tge::engine::{Engine, EngineBuilder};
 use tge::window::WindowConfig;
 use tge::graphics::*;
@@ -20,7 +20,7 @@ impl App {
 impl Game for App {
 
     fn update(&mut self, engine: &mut Engine) -> GameResult {
-        let title = format!("{} - FPS: {}", TITLE, engine.timer().real_time_fps().round());
+        let title = format!("{} - FPS: {}", TITLE, engine.timer().stats().average_fps().round());
         engine.window().set_title(title);
         Ok(())
     }
@@ -33,28 +33,33 @@ impl Game for App {
             for i in 0..10 {
                 let i = i as f32;
                 let j = j as f32;
-                engine.graphics().draw_sprite(
-                    NO_TEXTURE,
-                    SpriteDrawParams::default()
-                        .position((10.0 * i, 10.0 * j))
-                        .region((0.0, 0.0, 1.0, 1.0))
-                        .color(Color::BLACK),
+                engine.graphics().draw_circle(
+                    (10.0 * i, 10.0 * j),
+                    1.0,
+                    ShapeDrawParams::fill().color(Color::BLACK),
                 );
             }
         }
 
         // line
-        engine.graphics().draw_sprite(
-            NO_TEXTURE,
-            SpriteDrawParams::default()
-                .position((100.0, 100.0))
-                .region((0.0, 0.0, 1.0, 300.0))
-                .colors([
-                    Color::RED,
-                    Color::RED,
-                    Color::GREEN,
-                    Color::GREEN,
-                ]),
+        engine.graphics().draw_line(
+            (100.0, 100.0),
+            (100.0, 400.0),
+            ShapeDrawParams::stroke(4.0)
+                .line_cap(LineCap::Round)
+                .color(Color::RED),
+        );
+        engine.graphics().draw_polyline(
+            &[
+                Position::new(130.0, 100.0),
+                Position::new(200.0, 160.0),
+                Position::new(130.0, 220.0),
+                Position::new(200.0, 280.0),
+            ],
+            ShapeDrawParams::stroke(12.0)
+                .line_join(LineJoin::Round)
+                .line_cap(LineCap::Square)
+                .color(Color::BLUE),
         );
 
         // triangle
@@ -77,18 +82,71 @@ impl Game for App {
         ];
         engine.graphics().draw_mesh(NO_TEXTURE, PrimitiveType::Triangles, vertices, None);
 
+        // polygon
+        engine.graphics().draw_polygon(
+            &[
+                Position::new(250.0, 100.0),
+                Position::new(350.0, 120.0),
+                Position::new(330.0, 220.0),
+                Position::new(240.0, 200.0),
+            ],
+            ShapeDrawParams::stroke(6.0)
+                .line_join(LineJoin::Bevel)
+                .color(Color::MAGENTA),
+        );
+
         // rectangle
-        engine.graphics().draw_sprite(
-            NO_TEXTURE,
-            SpriteDrawParams::default()
-                .position((150.0, 350.0))
-                .region((0.0, 0.0, 300.0, 200.0))
-                .colors([
-                    Color::YELLOW,
-                    Color::YELLOW,
-                    Color::GREEN,
-                    Color::GREEN,
-                ]),
+        engine.graphics().draw_rect(
+            (150.0, 350.0, 300.0, 200.0),
+            ShapeDrawParams::fill().color(Color::YELLOW),
+        );
+        engine.graphics().draw_rect(
+            (150.0, 350.0, 300.0, 200.0),
+            ShapeDrawParams::stroke(8.0).color(Color::GREEN),
+        );
+
+        // circle, ellipse and arc
+        engine.graphics().draw_circle((560.0, 500.0), 60.0, ShapeDrawParams::fill().color(Color::CYAN));
+        engine.graphics().draw_ellipse((560.0, 500.0), (80.0, 40.0), ShapeDrawParams::stroke(3.0).color(Color::BLACK));
+        engine.graphics().draw_arc(
+            (720.0, 300.0),
+            50.0,
+            Angle::degrees(-90.0),
+            Angle::degrees(135.0),
+            ShapeDrawParams::fill().color(Color::GREEN),
+        );
+        engine.graphics().draw_arc(
+            (720.0, 440.0),
+            50.0,
+            Angle::degrees(0.0),
+            Angle::degrees(270.0),
+            ShapeDrawParams::stroke(10.0)
+                .line_cap(LineCap::Round)
+                .color(Color::RED),
+        );
+
+        // path
+        let mut star = Path::new().move_to((300.0, 20.0));
+        for i in 1..5 {
+            let angle = Angle::degrees(-90.0 + 144.0 * i as f32).radians_value();
+            star = star.line_to((300.0 + 60.0 * angle.cos(), 80.0 + 60.0 * angle.sin()));
+        }
+        let star = star.close();
+        engine.graphics().draw_path(
+            &star,
+            ShapeDrawParams::fill()
+                .fill_rule(FillRule::EvenOdd)
+                .color(Color::RED),
+        );
+        let curve = Path::new()
+            .move_to((420.0, 40.0))
+            .quad_to((480.0, 160.0), (540.0, 40.0))
+            .cubic_to((580.0, -20.0), (620.0, 120.0), (660.0, 40.0));
+        engine.graphics().draw_path(
+            &curve,
+            ShapeDrawParams::stroke(3.0)
+                .dash(&[12.0, 6.0])
+                .color(Color::BLACK),
         );
 
         Ok(())
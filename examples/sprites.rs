@@ -74,7 +74,7 @@ impl App {
 impl Game for App {
 
     fn update(&mut self, engine: &mut Engine) -> GameResult {
-        let title = format!("{}: {} - FPS: {}", TITLE, self.sprites.len(), engine.timer().real_time_fps().round());
+        let title = format!("{}: {} - FPS: {}", TITLE, self.sprites.len(), engine.timer().stats().average_fps().round());
         engine.window().set_title(title);
 
         let delta_time_f32 = engine.timer().delta_time().as_secs_f32();
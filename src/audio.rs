@@ -1,26 +1,336 @@
-use crate::error::GameResult;
+mod source;
+mod bus;
+mod spatial;
+mod sound;
+mod music;
+mod instance;
+mod play_params;
+mod output;
+mod progress;
 
-pub struct Audio {}
+use source::{RewindableSource, AudioState, PlaybackControls, ControlledSource};
+use bus::{BusState, BusMixer};
+use output::{OutputBackend, HeadlessOutput};
+
+pub use bus::AudioBus;
+pub use spatial::{Rolloff, Attenuation};
+pub use sound::Sound;
+pub use music::Music;
+pub use instance::AudioInstance;
+pub use play_params::AudioPlayParams;
+pub use output::AudioOutput;
+
+use crate::error::{GameError, GameResult};
+use crate::math::Position;
+use std::sync::Arc;
+use std::time::Duration;
+use std::collections::HashMap;
+
+pub struct Audio {
+    output: OutputBackend,
+    state: Arc<AudioState>,
+    default_attenuation: Attenuation,
+}
 
 impl Audio {
 
-    pub(crate) fn new(_: AudioConfig) -> GameResult<Self> {
-        Ok(Self {})
+    pub(crate) fn new(audio_config: AudioConfig) -> GameResult<Self> {
+        Ok(Self {
+            output: OutputBackend::new(audio_config.output),
+            state: Arc::new(AudioState::new(BusMixer::new(audio_config.buses))),
+            default_attenuation: audio_config.default_attenuation,
+        })
+    }
+
+    pub(crate) fn suspend(&mut self) {
+        self.state.set_suspended(true);
+    }
+
+    pub(crate) fn resume(&mut self) {
+        self.state.set_suspended(false);
+    }
+
+    pub fn is_available(&self) -> bool {
+        !matches!(self.output, OutputBackend::Unavailable)
+    }
+
+    pub fn is_headless(&self) -> bool {
+        matches!(self.output, OutputBackend::Headless(_))
+    }
+
+    fn headless_output(&mut self) -> GameResult<&mut HeadlessOutput> {
+        match &mut self.output {
+            OutputBackend::Headless(headless_output) => Ok(headless_output),
+            _ => Err(GameError::StateError("audio output is not headless".into())),
+        }
+    }
+
+    pub fn advance_headless(&mut self, duration: Duration) -> GameResult<&[f32]> {
+        let headless_output = self.headless_output()?;
+        Ok(headless_output.advance(duration))
+    }
+
+    pub fn headless_buffer(&mut self) -> GameResult<&[f32]> {
+        let headless_output = self.headless_output()?;
+        Ok(headless_output.buffer())
+    }
+
+    pub fn headless_peak(&mut self) -> GameResult<f32> {
+        let headless_output = self.headless_output()?;
+        Ok(output::peak(headless_output.buffer()))
+    }
+
+    pub fn clear_headless_buffer(&mut self) -> GameResult {
+        let headless_output = self.headless_output()?;
+        headless_output.clear_buffer();
+        Ok(())
+    }
+
+    pub fn bus_volume(&self, bus: &AudioBus) -> f32 {
+        self.state.mixer().state(bus).volume
+    }
+
+    pub fn set_bus_volume(&mut self, bus: &AudioBus, volume: f32) {
+        instance::assert_volume(volume);
+        self.state.mixer().set_volume(bus, volume);
+    }
+
+    pub fn is_bus_muted(&self, bus: &AudioBus) -> bool {
+        self.state.mixer().state(bus).muted
+    }
+
+    pub fn set_bus_muted(&mut self, bus: &AudioBus, muted: bool) {
+        self.state.mixer().set_muted(bus, muted);
+    }
+
+    pub fn master_volume(&self) -> f32 {
+        self.bus_volume(&AudioBus::Master)
+    }
+
+    pub fn set_master_volume(&mut self, volume: f32) {
+        self.set_bus_volume(&AudioBus::Master, volume);
+    }
+
+    pub fn listener_position(&self) -> Position {
+        self.state.listener_position()
+    }
+
+    pub fn set_listener_position(&mut self, position: impl Into<Position>) {
+        self.state.set_listener_position(position.into());
+    }
+
+    pub fn default_attenuation(&self) -> Attenuation {
+        self.default_attenuation
+    }
+
+    pub fn set_default_attenuation(&mut self, attenuation: Attenuation) {
+        self.default_attenuation = attenuation;
+    }
+
+    fn play_source(&mut self, source: impl RewindableSource + 'static, default_bus: AudioBus, params: AudioPlayParams) -> GameResult<AudioInstance> {
+        let bus = params.bus.unwrap_or(default_bus);
+        let bus_gain = self.state.mixer().bus_gain(&bus);
+        let controls = Arc::new(PlaybackControls::new(
+            bus,
+            bus_gain,
+            params.volume.unwrap_or(1.0),
+            params.speed.unwrap_or(1.0),
+            params.looping.unwrap_or(false),
+            params.position,
+            params.attenuation.unwrap_or(self.default_attenuation),
+        ));
+        controls.set_paused(params.paused.unwrap_or(false));
+        let source = ControlledSource::new(source, controls.clone(), self.state.clone());
+        match &self.output {
+            OutputBackend::Device(device) => rodio::play_raw(device, source),
+            OutputBackend::Headless(headless_output) => headless_output.add(source),
+            OutputBackend::Unavailable => return Err(GameError::NotSupportedError("no audio output device".into())),
+        }
+        Ok(AudioInstance::new(controls, self.state.clone()))
     }
 
-    pub(crate) fn suspend(&mut self) {}
+    pub fn play_sound(&mut self, sound: &Sound, params: AudioPlayParams) -> GameResult<AudioInstance> {
+        self.play_source(sound.source(), AudioBus::Sfx, params)
+    }
 
-    pub(crate) fn resume(&mut self) {}
+    pub fn play_music(&mut self, music: &Music, params: AudioPlayParams) -> GameResult<AudioInstance> {
+        let source = music.source()?;
+        self.play_source(source, AudioBus::Music, params)
+    }
 
 }
 
 #[derive(Debug, Clone)]
-pub struct AudioConfig {}
+pub struct AudioConfig {
+    output: AudioOutput,
+    buses: HashMap<AudioBus, BusState>,
+    default_attenuation: Attenuation,
+}
 
 impl AudioConfig {
 
     pub fn new() -> Self {
-        Self {}
+        Self {
+            output: AudioOutput::default(),
+            buses: HashMap::new(),
+            default_attenuation: Attenuation::default(),
+        }
+    }
+
+    pub fn output(mut self, output: AudioOutput) -> Self {
+        self.output = output;
+        self
+    }
+
+    pub fn bus_volume(mut self, bus: AudioBus, volume: f32) -> Self {
+        instance::assert_volume(volume);
+        self.buses.entry(bus).or_default().volume = volume;
+        self
+    }
+
+    pub fn bus_muted(mut self, bus: AudioBus, muted: bool) -> Self {
+        self.buses.entry(bus).or_default().muted = muted;
+        self
+    }
+
+    pub fn master_volume(self, volume: f32) -> Self {
+        self.bus_volume(AudioBus::Master, volume)
+    }
+
+    pub fn default_attenuation(mut self, attenuation: Attenuation) -> Self {
+        self.default_attenuation = attenuation;
+        self
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Audio, AudioConfig, AudioOutput, AudioBus, AudioPlayParams, AudioInstance, Attenuation, Sound};
+    use super::source::{AudioState, PlaybackControls};
+    use super::bus::BusMixer;
+    use super::output::peak;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use std::collections::HashMap;
+
+    fn headless_audio() -> Audio {
+        Audio::new(AudioConfig::new().output(AudioOutput::headless())).unwrap()
+    }
+
+    fn constant_sound(value: f32, duration: Duration) -> Sound {
+        let sample_count = (duration.as_secs_f64() * 44100.0) as usize;
+        Sound::from_samples(1, 44100, vec![value; sample_count]).unwrap()
+    }
+
+    #[test]
+    fn bus_volume() {
+        let mut audio = headless_audio();
+        let sound = constant_sound(0.5, Duration::from_secs(1));
+        audio.play_sound(&sound, AudioPlayParams::default()).unwrap();
+        let samples = audio.advance_headless(Duration::from_millis(100)).unwrap();
+        assert!((peak(samples) - 0.5).abs() < 1e-4);
+
+        audio.set_bus_volume(&AudioBus::Sfx, 0.5);
+        audio.advance_headless(Duration::from_millis(50)).unwrap();
+        let samples = audio.advance_headless(Duration::from_millis(50)).unwrap();
+        assert!((peak(samples) - 0.25).abs() < 1e-4);
+
+        audio.set_bus_muted(&AudioBus::Master, true);
+        audio.advance_headless(Duration::from_millis(50)).unwrap();
+        let samples = audio.advance_headless(Duration::from_millis(50)).unwrap();
+        assert_eq!(peak(samples), 0.0);
+    }
+
+    #[test]
+    fn headless_buffer() {
+        let mut audio = headless_audio();
+        let sound = constant_sound(0.5, Duration::from_secs(1));
+        audio.play_sound(&sound, AudioPlayParams::default()).unwrap();
+        audio.advance_headless(Duration::from_millis(100)).unwrap();
+        let sample_count = audio.advance_headless(Duration::from_millis(10)).unwrap().len();
+        assert!(sample_count > 0);
+        assert_eq!(audio.headless_buffer().unwrap().len(), sample_count);
+        assert!((audio.headless_peak().unwrap() - 0.5).abs() < 1e-4);
+        audio.clear_headless_buffer().unwrap();
+        assert!(audio.headless_buffer().unwrap().is_empty());
+    }
+
+    #[test]
+    fn looping() {
+        let mut audio = headless_audio();
+        let sound = constant_sound(0.5, Duration::from_millis(10));
+        let once = audio.play_sound(&sound, AudioPlayParams::default()).unwrap();
+        let looped = audio.play_sound(&sound, AudioPlayParams::default().bus(AudioBus::Music).looping(true)).unwrap();
+        audio.advance_headless(Duration::from_millis(100)).unwrap();
+        assert!(once.is_finished());
+        assert!(!looped.is_finished());
+        assert!(looped.elapsed() < Duration::from_millis(10));
+        let samples = audio.advance_headless(Duration::from_millis(50)).unwrap();
+        assert!((peak(samples) - 0.5).abs() < 1e-4);
+
+        looped.stop();
+        audio.advance_headless(Duration::from_millis(10)).unwrap();
+        assert!(looped.is_finished());
+    }
+
+    #[test]
+    fn fade_and_pause() {
+        let mut audio = headless_audio();
+        let sound = constant_sound(0.5, Duration::from_secs(1));
+        let instance = audio.play_sound(&sound, AudioPlayParams::default()).unwrap();
+        instance.fade_to(0.0, Duration::from_millis(50));
+        audio.advance_headless(Duration::from_millis(100)).unwrap();
+        assert!(!instance.is_fading());
+        assert_eq!(instance.volume(), 0.0);
+        let samples = audio.advance_headless(Duration::from_millis(50)).unwrap();
+        assert_eq!(peak(samples), 0.0);
+
+        instance.set_volume(1.0);
+        instance.pause();
+        audio.advance_headless(Duration::from_millis(50)).unwrap();
+        let elapsed = instance.elapsed();
+        let samples = audio.advance_headless(Duration::from_millis(50)).unwrap();
+        assert_eq!(peak(samples), 0.0);
+        assert_eq!(instance.elapsed(), elapsed);
+        assert!(!instance.is_finished());
+    }
+
+    #[test]
+    fn fade_with_speed() {
+        let mut audio = headless_audio();
+        let sound = constant_sound(0.5, Duration::from_secs(1));
+        let instance = audio.play_sound(&sound, AudioPlayParams::default().speed(2.0)).unwrap();
+        instance.fade_to(0.0, Duration::from_millis(200));
+        audio.advance_headless(Duration::from_millis(100)).unwrap();
+        assert!(instance.is_fading());
+        audio.advance_headless(Duration::from_millis(150)).unwrap();
+        assert!(!instance.is_fading());
+    }
+
+    #[test]
+    fn release_paused() {
+        let state = Arc::new(AudioState::new(BusMixer::new(HashMap::new())));
+        let bus_gain = state.mixer().bus_gain(&AudioBus::Sfx);
+        let controls = Arc::new(PlaybackControls::new(AudioBus::Sfx, bus_gain, 1.0, 1.0, false, None, Attenuation::default()));
+        controls.set_paused(true);
+        let instance = AudioInstance::new(controls.clone(), state.clone());
+        let other = instance.clone();
+        drop(instance);
+        assert!(!controls.is_stopped());
+        drop(other);
+        assert!(controls.is_stopped());
+
+        let bus_gain = state.mixer().bus_gain(&AudioBus::Sfx);
+        let controls = Arc::new(PlaybackControls::new(AudioBus::Sfx, bus_gain, 1.0, 1.0, false, None, Attenuation::default()));
+        let instance = AudioInstance::new(controls.clone(), state.clone());
+        assert!(instance.is_playing());
+        state.set_suspended(true);
+        assert!(!instance.is_playing());
+        state.set_suspended(false);
+        drop(instance);
+        assert!(!controls.is_stopped());
     }
 
 }
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub enum AudioBus {
+    Master,
+    Music,
+    Sfx,
+    Voice,
+    Custom(String),
+}
+
+impl AudioBus {
+
+    pub fn custom(name: impl Into<String>) -> Self {
+        AudioBus::Custom(name.into())
+    }
+
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct BusState {
+    pub volume: f32,
+    pub muted: bool,
+}
+
+impl Default for BusState {
+
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+        }
+    }
+
+}
+
+#[derive(Debug)]
+pub(crate) struct BusGain {
+    bits: AtomicU32,
+}
+
+impl BusGain {
+
+    fn new(gain: f32) -> Self {
+        Self {
+            bits: AtomicU32::new(gain.to_bits()),
+        }
+    }
+
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.bits.load(Ordering::Relaxed))
+    }
+
+    fn set(&self, gain: f32) {
+        self.bits.store(gain.to_bits(), Ordering::Relaxed);
+    }
+
+}
+
+#[derive(Debug)]
+pub(crate) struct BusMixer {
+    buses: HashMap<AudioBus, BusState>,
+    gains: HashMap<AudioBus, Arc<BusGain>>,
+}
+
+impl BusMixer {
+
+    pub fn new(buses: HashMap<AudioBus, BusState>) -> Self {
+        Self {
+            buses,
+            gains: HashMap::new(),
+        }
+    }
+
+    pub fn state(&self, bus: &AudioBus) -> BusState {
+        self.buses.get(bus).copied().unwrap_or_default()
+    }
+
+    pub fn set_volume(&mut self, bus: &AudioBus, volume: f32) {
+        self.buses.entry(bus.clone()).or_default().volume = volume;
+        self.update_gains();
+    }
+
+    pub fn set_muted(&mut self, bus: &AudioBus, muted: bool) {
+        self.buses.entry(bus.clone()).or_default().muted = muted;
+        self.update_gains();
+    }
+
+    pub fn bus_gain(&mut self, bus: &AudioBus) -> Arc<BusGain> {
+        if let Some(bus_gain) = self.gains.get(bus) {
+            return bus_gain.clone();
+        }
+        let bus_gain = Arc::new(BusGain::new(self.gain(bus)));
+        self.gains.insert(bus.clone(), bus_gain.clone());
+        bus_gain
+    }
+
+    fn update_gains(&self) {
+        // the master bus scales every other bus, so refresh them all
+        for (bus, bus_gain) in &self.gains {
+            bus_gain.set(self.gain(bus));
+        }
+    }
+
+    pub fn gain(&self, bus: &AudioBus) -> f32 {
+        let master = self.state(&AudioBus::Master);
+        if master.muted {
+            return 0.0;
+        }
+        match bus {
+            AudioBus::Master => master.volume,
+            bus => {
+                let state = self.state(bus);
+                if state.muted {
+                    0.0
+                } else {
+                    master.volume * state.volume
+                }
+            }
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{AudioBus, BusMixer};
+    use std::collections::HashMap;
+
+    #[test]
+    fn gain() {
+        let mut mixer = BusMixer::new(HashMap::new());
+        assert_eq!(mixer.gain(&AudioBus::Sfx), 1.0);
+        let sfx_gain = mixer.bus_gain(&AudioBus::Sfx);
+        mixer.set_volume(&AudioBus::Master, 0.5);
+        mixer.set_volume(&AudioBus::Music, 0.5);
+        assert_eq!(mixer.gain(&AudioBus::Master), 0.5);
+        assert_eq!(mixer.gain(&AudioBus::Music), 0.25);
+        assert_eq!(mixer.gain(&AudioBus::custom("ambience")), 0.5);
+        mixer.set_muted(&AudioBus::Music, true);
+        assert_eq!(mixer.gain(&AudioBus::Music), 0.0);
+        assert_eq!(mixer.gain(&AudioBus::Sfx), 0.5);
+        assert_eq!(sfx_gain.get(), 0.5);
+        assert_eq!(mixer.bus_gain(&AudioBus::Music).get(), 0.0);
+        mixer.set_muted(&AudioBus::Master, true);
+        assert_eq!(mixer.gain(&AudioBus::Sfx), 0.0);
+        assert_eq!(sfx_gain.get(), 0.0);
+    }
+
+}
@@ -0,0 +1,134 @@
+use super::{AudioBus, Attenuation};
+use crate::math::Position;
+use super::source::{PlaybackControls, AudioState};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub struct AudioInstance {
+    controls: Arc<PlaybackControls>,
+    state: Arc<AudioState>,
+}
+
+impl AudioInstance {
+
+    pub(crate) fn new(controls: Arc<PlaybackControls>, state: Arc<AudioState>) -> Self {
+        controls.acquire_handle();
+        Self { controls, state }
+    }
+
+    pub fn pause(&self) {
+        self.controls.set_paused(true);
+    }
+
+    pub fn resume(&self) {
+        self.controls.set_paused(false);
+    }
+
+    pub fn stop(&self) {
+        self.controls.stop();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.controls.is_paused()
+    }
+
+    pub fn is_playing(&self) -> bool {
+        !self.controls.is_paused() && !self.state.is_suspended() && !self.is_finished()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.controls.is_stopped() || self.controls.is_finished()
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.controls.is_looping()
+    }
+
+    pub fn set_looping(&self, looping: bool) {
+        self.controls.set_looping(looping);
+    }
+
+    pub fn bus(&self) -> AudioBus {
+        self.controls.bus()
+    }
+
+    pub fn set_bus(&self, bus: AudioBus) {
+        let bus_gain = self.state.mixer().bus_gain(&bus);
+        self.controls.set_bus(bus, bus_gain);
+    }
+
+    pub fn position(&self) -> Option<Position> {
+        self.controls.position()
+    }
+
+    pub fn set_position(&self, position: Option<impl Into<Position>>) {
+        self.controls.set_position(position.map(|position| position.into()));
+    }
+
+    pub fn attenuation(&self) -> Attenuation {
+        self.controls.attenuation()
+    }
+
+    pub fn set_attenuation(&self, attenuation: Attenuation) {
+        self.controls.set_attenuation(attenuation);
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.controls.volume()
+    }
+
+    pub fn set_volume(&self, volume: f32) {
+        assert_volume(volume);
+        self.controls.set_volume(volume);
+    }
+
+    pub fn fade_to(&self, volume: f32, duration: Duration) {
+        assert_volume(volume);
+        self.controls.fade_to(volume, duration);
+    }
+
+    pub fn is_fading(&self) -> bool {
+        self.controls.is_fading()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.controls.elapsed()
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.controls.speed()
+    }
+
+    pub fn set_speed(&self, speed: f32) {
+        assert_speed(speed);
+        self.controls.set_speed(speed);
+    }
+
+}
+
+impl Clone for AudioInstance {
+
+    fn clone(&self) -> Self {
+        Self::new(self.controls.clone(), self.state.clone())
+    }
+
+}
+
+impl Drop for AudioInstance {
+
+    fn drop(&mut self) {
+        // nothing can resume a paused source once the last handle is gone
+        if self.controls.release_handle() && self.controls.is_paused() {
+            self.controls.stop();
+        }
+    }
+
+}
+
+pub(crate) fn assert_volume(volume: f32) {
+    assert!(volume >= 0.0, "volume must >= 0.0");
+}
+
+pub(crate) fn assert_speed(speed: f32) {
+    assert!(speed > 0.0, "speed must > 0.0");
+}
@@ -0,0 +1,90 @@
+use super::source::RewindableSource;
+use super::sound::convert_sample;
+use crate::error::{GameError, GameResult};
+use crate::engine::Engine;
+use rodio::{Decoder, Source};
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+use std::path::Path;
+
+type MusicDecoder = Decoder<Cursor<Arc<[u8]>>>;
+
+#[derive(Clone)]
+pub struct Music {
+    bytes: Arc<[u8]>,
+}
+
+impl Music {
+
+    pub fn from_bytes(bytes: &[u8]) -> GameResult<Self> {
+        let bytes: Arc<[u8]> = bytes.into();
+        Decoder::new(Cursor::new(bytes.clone()))
+            .map_err(|error| GameError::InitError(Box::new(error)))?;
+        Ok(Self { bytes })
+    }
+
+    pub fn load(engine: &mut Engine, path: impl AsRef<Path>) -> GameResult<Self> {
+        let bytes = engine.filesystem().read(path)?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub(crate) fn source(&self) -> GameResult<MusicSource> {
+        let decoder = Decoder::new(Cursor::new(self.bytes.clone()))
+            .map_err(|error| GameError::RuntimeError(Box::new(error)))?;
+        Ok(MusicSource {
+            bytes: self.bytes.clone(),
+            decoder,
+        })
+    }
+
+}
+
+pub(crate) struct MusicSource {
+    bytes: Arc<[u8]>,
+    decoder: MusicDecoder,
+}
+
+impl Iterator for MusicSource {
+
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decoder.next().map(convert_sample)
+    }
+
+}
+
+impl Source for MusicSource {
+
+    fn current_frame_len(&self) -> Option<usize> {
+        self.decoder.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.decoder.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.decoder.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.decoder.total_duration()
+    }
+
+}
+
+impl RewindableSource for MusicSource {
+
+    fn rewind(&mut self) -> bool {
+        match Decoder::new(Cursor::new(self.bytes.clone())) {
+            Ok(decoder) => {
+                self.decoder = decoder;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+}
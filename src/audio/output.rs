@@ -0,0 +1,109 @@
+use rodio::Source;
+use rodio::dynamic_mixer::{self, DynamicMixerController, DynamicMixer};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum AudioOutput {
+    Default,
+    Headless {
+        channels: u16,
+        sample_rate: u32,
+    },
+}
+
+impl AudioOutput {
+
+    pub fn headless() -> Self {
+        AudioOutput::Headless {
+            channels: 2,
+            sample_rate: 44100,
+        }
+    }
+
+}
+
+impl Default for AudioOutput {
+
+    fn default() -> Self {
+        AudioOutput::Default
+    }
+
+}
+
+pub enum OutputBackend {
+    Device(rodio::Device),
+    Headless(HeadlessOutput),
+    Unavailable,
+}
+
+impl OutputBackend {
+
+    pub fn new(output: AudioOutput) -> Self {
+        match output {
+            AudioOutput::Default => match rodio::default_output_device() {
+                Some(device) => OutputBackend::Device(device),
+                None => OutputBackend::Unavailable,
+            },
+            AudioOutput::Headless { channels, sample_rate } => OutputBackend::Headless(HeadlessOutput::new(channels, sample_rate)),
+        }
+    }
+
+}
+
+pub struct HeadlessOutput {
+    controller: Arc<DynamicMixerController<f32>>,
+    mixer: DynamicMixer<f32>,
+    channels: u16,
+    sample_rate: u32,
+    frame_remainder: f64,
+    buffer: Vec<f32>,
+}
+
+impl HeadlessOutput {
+
+    pub fn new(channels: u16, sample_rate: u32) -> Self {
+        assert!(channels > 0, "channels must > 0");
+        assert!(sample_rate > 0, "sample rate must > 0");
+        let (controller, mixer) = dynamic_mixer::mixer(channels, sample_rate);
+        Self {
+            controller,
+            mixer,
+            channels,
+            sample_rate,
+            frame_remainder: 0.0,
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn add(&self, source: impl Source<Item = f32> + Send + 'static) {
+        self.controller.add(source);
+    }
+
+    pub fn advance(&mut self, duration: Duration) -> &[f32] {
+        let frames = duration.as_secs_f64() * self.sample_rate as f64 + self.frame_remainder;
+        self.frame_remainder = frames.fract();
+        let sample_count = frames.trunc() as usize * self.channels as usize;
+        // only the last advance is kept, so a long headless run does not grow the buffer
+        self.buffer.clear();
+        self.buffer.reserve(sample_count);
+        for _ in 0..sample_count {
+            let sample = self.mixer.next().unwrap_or(0.0);
+            self.buffer.push(sample);
+        }
+        &self.buffer
+    }
+
+    pub fn buffer(&self) -> &[f32] {
+        &self.buffer
+    }
+
+    pub fn clear_buffer(&mut self) {
+        self.buffer.clear();
+    }
+
+}
+
+pub fn peak(samples: &[f32]) -> f32 {
+    samples.iter().fold(0.0, |peak, sample| peak.max(sample.abs()))
+}
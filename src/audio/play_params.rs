@@ -0,0 +1,55 @@
+use super::{AudioBus, Attenuation};
+use crate::math::Position;
+use super::instance::{assert_volume, assert_speed};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AudioPlayParams {
+    pub bus: Option<AudioBus>,
+    pub volume: Option<f32>,
+    pub speed: Option<f32>,
+    pub looping: Option<bool>,
+    pub paused: Option<bool>,
+    pub position: Option<Position>,
+    pub attenuation: Option<Attenuation>,
+}
+
+impl AudioPlayParams {
+
+    pub fn bus(mut self, bus: AudioBus) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
+    pub fn volume(mut self, volume: f32) -> Self {
+        assert_volume(volume);
+        self.volume = Some(volume);
+        self
+    }
+
+    pub fn speed(mut self, speed: f32) -> Self {
+        assert_speed(speed);
+        self.speed = Some(speed);
+        self
+    }
+
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.looping = Some(looping);
+        self
+    }
+
+    pub fn paused(mut self, paused: bool) -> Self {
+        self.paused = Some(paused);
+        self
+    }
+
+    pub fn position(mut self, position: impl Into<Position>) -> Self {
+        self.position = Some(position.into());
+        self
+    }
+
+    pub fn attenuation(mut self, attenuation: Attenuation) -> Self {
+        self.attenuation = Some(attenuation);
+        self
+    }
+
+}
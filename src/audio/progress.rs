@@ -0,0 +1,90 @@
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct Fade {
+    target: f32,
+    rate: f32,
+}
+
+impl Fade {
+
+    pub fn new(volume: f32, target: f32, duration: Duration) -> Option<Self> {
+        let duration = duration.as_secs_f32();
+        if duration > 0.0 {
+            Some(Self {
+                target,
+                rate: (target - volume).abs() / duration,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    pub fn advance(&self, volume: f32, seconds: f32) -> Option<f32> {
+        let step = self.rate * seconds;
+        if (self.target - volume).abs() <= step {
+            None
+        } else if self.target > volume {
+            Some(volume + step)
+        } else {
+            Some(volume - step)
+        }
+    }
+
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Elapsed {
+    frames: AtomicU64,
+    sample_rate: AtomicU32,
+}
+
+impl Elapsed {
+
+    pub fn store(&self, frames: u64, sample_rate: u32) {
+        self.frames.store(frames, Ordering::Relaxed);
+        self.sample_rate.store(sample_rate, Ordering::Relaxed);
+    }
+
+    pub fn duration(&self) -> Duration {
+        let sample_rate = self.sample_rate.load(Ordering::Relaxed);
+        if sample_rate > 0 {
+            Duration::from_secs_f64(self.frames.load(Ordering::Relaxed) as f64 / sample_rate as f64)
+        } else {
+            Duration::new(0, 0)
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Fade, Elapsed};
+    use std::time::Duration;
+
+    #[test]
+    fn fade() {
+        assert_eq!(Fade::new(1.0, 0.0, Duration::new(0, 0)), None);
+        let fade = Fade::new(1.0, 0.0, Duration::from_secs(2)).unwrap();
+        assert_eq!(fade.advance(1.0, 1.0), Some(0.5));
+        assert_eq!(fade.advance(0.5, 1.0), None);
+        assert_eq!(fade.target(), 0.0);
+        let fade = Fade::new(0.0, 1.0, Duration::from_secs(4)).unwrap();
+        assert_eq!(fade.advance(0.0, 1.0), Some(0.25));
+    }
+
+    #[test]
+    fn elapsed() {
+        let elapsed = Elapsed::default();
+        assert_eq!(elapsed.duration(), Duration::new(0, 0));
+        elapsed.store(22050, 44100);
+        assert_eq!(elapsed.duration(), Duration::from_millis(500));
+    }
+
+}
@@ -0,0 +1,129 @@
+use super::source::RewindableSource;
+use crate::error::{GameError, GameResult};
+use crate::engine::Engine;
+use rodio::{Decoder, Source};
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+use std::path::Path;
+
+struct SoundData {
+    samples: Vec<f32>,
+    channels: u16,
+    sample_rate: u32,
+}
+
+#[derive(Clone)]
+pub struct Sound {
+    data: Arc<SoundData>,
+}
+
+impl Sound {
+
+    pub fn from_samples(channels: u16, sample_rate: u32, samples: Vec<f32>) -> GameResult<Self> {
+        if channels == 0 || sample_rate == 0 {
+            return Err(GameError::InitError("channels and sample rate must > 0".into()));
+        }
+        if samples.len() % channels as usize != 0 {
+            return Err(GameError::InitError("illegal samples length".into()));
+        }
+        Ok(Self {
+            data: Arc::new(SoundData {
+                samples,
+                channels,
+                sample_rate,
+            }),
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> GameResult<Self> {
+        let decoder = Decoder::new(Cursor::new(bytes.to_vec()))
+            .map_err(|error| GameError::InitError(Box::new(error)))?;
+        let channels = decoder.channels();
+        let sample_rate = decoder.sample_rate();
+        let samples = decoder.map(convert_sample).collect();
+        Self::from_samples(channels, sample_rate, samples)
+    }
+
+    pub fn load(engine: &mut Engine, path: impl AsRef<Path>) -> GameResult<Self> {
+        let bytes = engine.filesystem().read(path)?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.data.channels
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.data.sample_rate
+    }
+
+    pub fn duration(&self) -> Duration {
+        samples_to_duration(self.data.samples.len(), self.data.channels, self.data.sample_rate)
+    }
+
+    pub(crate) fn source(&self) -> SoundSource {
+        SoundSource {
+            data: self.data.clone(),
+            position: 0,
+        }
+    }
+
+}
+
+pub(crate) struct SoundSource {
+    data: Arc<SoundData>,
+    position: usize,
+}
+
+impl Iterator for SoundSource {
+
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.data.samples.get(self.position).copied();
+        if sample.is_some() {
+            self.position += 1;
+        }
+        sample
+    }
+
+}
+
+impl Source for SoundSource {
+
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.data.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.data.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(samples_to_duration(self.data.samples.len(), self.data.channels, self.data.sample_rate))
+    }
+
+}
+
+impl RewindableSource for SoundSource {
+
+    fn rewind(&mut self) -> bool {
+        self.position = 0;
+        !self.data.samples.is_empty()
+    }
+
+}
+
+pub(crate) fn convert_sample(sample: i16) -> f32 {
+    sample as f32 / 32768.0
+}
+
+fn samples_to_duration(samples: usize, channels: u16, sample_rate: u32) -> Duration {
+    let frames = samples as u64 / channels.max(1) as u64;
+    Duration::from_secs_f64(frames as f64 / sample_rate.max(1) as f64)
+}
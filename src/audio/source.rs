@@ -0,0 +1,395 @@
+use super::bus::{AudioBus, BusMixer, BusGain};
+use super::progress::{Fade, Elapsed};
+use super::spatial::{Attenuation, SpatialGains, spatial_gains};
+use crate::math::Position;
+use rodio::Source;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+const CONTROL_REFRESH_FRAMES: usize = 256;
+
+pub trait RewindableSource: Source<Item = f32> + Send {
+
+    fn rewind(&mut self) -> bool;
+
+}
+
+pub struct AudioState {
+    suspended: AtomicBool,
+    mixer: Mutex<BusMixer>,
+    listener_position: Mutex<Position>,
+}
+
+impl AudioState {
+
+    pub fn new(mixer: BusMixer) -> Self {
+        Self {
+            suspended: AtomicBool::new(false),
+            mixer: Mutex::new(mixer),
+            listener_position: Mutex::new(Position::zero()),
+        }
+    }
+
+    pub fn mixer(&self) -> MutexGuard<'_, BusMixer> {
+        self.mixer.lock().unwrap()
+    }
+
+    pub fn listener_position(&self) -> Position {
+        *self.listener_position.lock().unwrap()
+    }
+
+    pub fn set_listener_position(&self, position: Position) {
+        *self.listener_position.lock().unwrap() = position;
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.suspended.load(Ordering::SeqCst)
+    }
+
+    pub fn set_suspended(&self, suspended: bool) {
+        self.suspended.store(suspended, Ordering::SeqCst);
+    }
+
+}
+
+pub struct PlaybackControls {
+    paused: AtomicBool,
+    stopped: AtomicBool,
+    looping: AtomicBool,
+    finished: AtomicBool,
+    handles: AtomicUsize,
+    volume: Mutex<f32>,
+    speed: Mutex<f32>,
+    bus: Mutex<AudioBus>,
+    bus_gain: Mutex<Arc<BusGain>>,
+    bus_changed: AtomicBool,
+    position: Mutex<Option<Position>>,
+    attenuation: Mutex<Attenuation>,
+    fade: Mutex<Option<Fade>>,
+    elapsed: Elapsed,
+}
+
+impl PlaybackControls {
+
+    pub(crate) fn new(bus: AudioBus, bus_gain: Arc<BusGain>, volume: f32, speed: f32, looping: bool, position: Option<Position>, attenuation: Attenuation) -> Self {
+        Self {
+            paused: AtomicBool::new(false),
+            stopped: AtomicBool::new(false),
+            looping: AtomicBool::new(looping),
+            finished: AtomicBool::new(false),
+            handles: AtomicUsize::new(0),
+            volume: Mutex::new(volume),
+            speed: Mutex::new(speed),
+            bus: Mutex::new(bus),
+            bus_gain: Mutex::new(bus_gain),
+            bus_changed: AtomicBool::new(false),
+            position: Mutex::new(position),
+            attenuation: Mutex::new(attenuation),
+            fade: Mutex::new(None),
+            elapsed: Elapsed::default(),
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.looping.load(Ordering::SeqCst)
+    }
+
+    pub fn set_looping(&self, looping: bool) {
+        self.looping.store(looping, Ordering::SeqCst);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::SeqCst)
+    }
+
+    fn finish(&self) {
+        self.finished.store(true, Ordering::SeqCst);
+    }
+
+    pub fn acquire_handle(&self) {
+        self.handles.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn release_handle(&self) -> bool {
+        self.handles.fetch_sub(1, Ordering::SeqCst) == 1
+    }
+
+    pub fn volume(&self) -> f32 {
+        *self.volume.lock().unwrap()
+    }
+
+    pub fn set_volume(&self, volume: f32) {
+        *self.fade.lock().unwrap() = None;
+        *self.volume.lock().unwrap() = volume;
+    }
+
+    pub fn fade_to(&self, target: f32, duration: Duration) {
+        match Fade::new(self.volume(), target, duration) {
+            Some(fade) => *self.fade.lock().unwrap() = Some(fade),
+            None => self.set_volume(target),
+        }
+    }
+
+    pub fn is_fading(&self) -> bool {
+        self.fade.lock().unwrap().is_some()
+    }
+
+    fn advance_fade(&self, seconds: f32) {
+        let mut fade = self.fade.lock().unwrap();
+        if let Some(current_fade) = *fade {
+            let mut volume = self.volume.lock().unwrap();
+            match current_fade.advance(*volume, seconds) {
+                Some(next_volume) => *volume = next_volume,
+                None => {
+                    *volume = current_fade.target();
+                    *fade = None;
+                }
+            }
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed.duration()
+    }
+
+    pub fn speed(&self) -> f32 {
+        *self.speed.lock().unwrap()
+    }
+
+    pub fn set_speed(&self, speed: f32) {
+        *self.speed.lock().unwrap() = speed;
+    }
+
+    pub fn bus(&self) -> AudioBus {
+        self.bus.lock().unwrap().clone()
+    }
+
+    pub(crate) fn set_bus(&self, bus: AudioBus, bus_gain: Arc<BusGain>) {
+        *self.bus.lock().unwrap() = bus;
+        *self.bus_gain.lock().unwrap() = bus_gain;
+        self.bus_changed.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn bus_gain(&self) -> Arc<BusGain> {
+        self.bus_gain.lock().unwrap().clone()
+    }
+
+    pub fn position(&self) -> Option<Position> {
+        *self.position.lock().unwrap()
+    }
+
+    pub fn set_position(&self, position: Option<Position>) {
+        *self.position.lock().unwrap() = position;
+    }
+
+    pub fn attenuation(&self) -> Attenuation {
+        *self.attenuation.lock().unwrap()
+    }
+
+    pub fn set_attenuation(&self, attenuation: Attenuation) {
+        *self.attenuation.lock().unwrap() = attenuation;
+    }
+
+}
+
+pub struct ControlledSource<S: RewindableSource> {
+    source: S,
+    controls: Arc<PlaybackControls>,
+    state: Arc<AudioState>,
+    bus_gain: Arc<BusGain>,
+    paused: bool,
+    volume: f32,
+    speed: f32,
+    spatial_gains: SpatialGains,
+    channel: usize,
+    mono_sample: f32,
+    consumed_samples: u64,
+    total_frames: Option<u64>,
+    frame_remaining: usize,
+}
+
+impl<S: RewindableSource> ControlledSource<S> {
+
+    pub fn new(source: S, controls: Arc<PlaybackControls>, state: Arc<AudioState>) -> Self {
+        let mut controlled_source = Self {
+            source,
+            bus_gain: controls.bus_gain(),
+            controls,
+            state,
+            paused: false,
+            volume: 1.0,
+            speed: 1.0,
+            spatial_gains: SpatialGains::default(),
+            channel: 0,
+            mono_sample: 0.0,
+            consumed_samples: 0,
+            total_frames: None,
+            frame_remaining: 0,
+        };
+        controlled_source.total_frames = controlled_source.source_total_frames();
+        controlled_source.frame_remaining = CONTROL_REFRESH_FRAMES * controlled_source.output_channels() as usize;
+        controlled_source.refresh();
+        controlled_source
+    }
+
+    fn is_mono(&self) -> bool {
+        self.source.channels() == 1
+    }
+
+    fn output_channels(&self) -> u16 {
+        self.source.channels().max(2)
+    }
+
+    fn source_total_frames(&self) -> Option<u64> {
+        let duration = self.source.total_duration()?;
+        Some((duration.as_secs_f64() * self.source.sample_rate() as f64).round() as u64)
+    }
+
+    fn refresh(&mut self) {
+        if !self.paused {
+            let refreshed_frames = CONTROL_REFRESH_FRAMES - self.frame_remaining / self.output_channels() as usize;
+            // the output pulls source frames at sample_rate * speed, see Source::sample_rate below
+            let seconds = refreshed_frames as f32 / (self.source.sample_rate().max(1) as f32 * self.speed);
+            self.controls.advance_fade(seconds);
+        }
+        self.paused = self.controls.is_paused() || self.state.is_suspended();
+        if self.controls.bus_changed.swap(false, Ordering::SeqCst) {
+            self.bus_gain = self.controls.bus_gain();
+        }
+        self.volume = self.controls.volume() * self.bus_gain.get();
+        self.speed = self.controls.speed();
+        self.spatial_gains = match self.controls.position() {
+            Some(position) => spatial_gains(self.state.listener_position(), position, &self.controls.attenuation()),
+            None => SpatialGains::default(),
+        };
+        self.frame_remaining = CONTROL_REFRESH_FRAMES * self.output_channels() as usize;
+    }
+
+    fn next_source_sample(&mut self) -> Option<f32> {
+        let sample = match self.source.next() {
+            Some(sample) => Some(sample),
+            None => {
+                if self.controls.is_looping() && self.source.rewind() {
+                    self.consumed_samples = 0;
+                    self.total_frames = self.source_total_frames();
+                    self.source.next()
+                } else {
+                    None
+                }
+            }
+        };
+        match sample {
+            Some(_) => {
+                self.consumed_samples += 1;
+                let channels = self.source.channels().max(1) as u64;
+                let mut frames = self.consumed_samples / channels;
+                // the source only restarts on the next pull, so a looping source wraps at its end already
+                if let Some(total_frames) = self.total_frames {
+                    if self.controls.is_looping() && total_frames > 0 {
+                        frames %= total_frames;
+                    }
+                }
+                self.controls.elapsed.store(frames, self.source.sample_rate());
+            }
+            None => self.controls.finish(),
+        }
+        sample
+    }
+
+}
+
+impl<S: RewindableSource> Iterator for ControlledSource<S> {
+
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.frame_remaining == 0 {
+            self.refresh();
+        }
+        if self.controls.is_stopped() {
+            self.controls.finish();
+            return None;
+        }
+        let channel = self.channel;
+        let sample = if self.paused {
+            0.0
+        } else if self.is_mono() {
+            if channel == 0 {
+                self.mono_sample = self.next_source_sample()?;
+            }
+            self.mono_sample
+        } else {
+            self.next_source_sample()?
+        };
+        self.channel = (channel + 1) % self.output_channels() as usize;
+        self.frame_remaining -= 1;
+        let pan_gain = match channel {
+            0 => self.spatial_gains.left,
+            1 => self.spatial_gains.right,
+            _ => 1.0,
+        };
+        let gain = self.spatial_gains.distance * pan_gain;
+        Some(sample * gain * self.volume)
+    }
+
+}
+
+impl<S: RewindableSource> Source for ControlledSource<S> {
+
+    fn current_frame_len(&self) -> Option<usize> {
+        let frame_remaining = if self.frame_remaining == 0 {
+            CONTROL_REFRESH_FRAMES * self.output_channels() as usize
+        } else {
+            self.frame_remaining
+        };
+        match self.source.current_frame_len() {
+            Some(frame_len) if frame_len > 0 => {
+                let frame_len = if self.is_mono() {
+                    frame_len * 2 + self.channel
+                } else {
+                    frame_len
+                };
+                Some(frame_len.min(frame_remaining))
+            }
+            _ => Some(frame_remaining),
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        self.output_channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        ((self.source.sample_rate() as f32 * self.speed) as u32).max(1)
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+}
+
+impl<S: RewindableSource> Drop for ControlledSource<S> {
+
+    fn drop(&mut self) {
+        self.controls.finish();
+    }
+
+}
@@ -0,0 +1,151 @@
+use crate::math::Position;
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum Rolloff {
+    None,
+    Linear,
+    Inverse,
+    Exponential,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Attenuation {
+    pub rolloff: Rolloff,
+    pub reference_distance: f32,
+    pub max_distance: f32,
+    pub rolloff_factor: f32,
+}
+
+impl Attenuation {
+
+    pub fn new(rolloff: Rolloff, reference_distance: f32, max_distance: f32) -> Self {
+        assert!(reference_distance > 0.0, "reference distance must > 0.0");
+        assert!(max_distance >= reference_distance, "max distance must >= reference distance");
+        Self {
+            rolloff,
+            reference_distance,
+            max_distance,
+            rolloff_factor: 1.0,
+        }
+    }
+
+    pub fn rolloff_factor(mut self, rolloff_factor: f32) -> Self {
+        assert!(rolloff_factor >= 0.0, "rolloff factor must >= 0.0");
+        self.rolloff_factor = rolloff_factor;
+        self
+    }
+
+    pub fn gain(&self, distance: f32) -> f32 {
+        let distance = distance.max(self.reference_distance).min(self.max_distance);
+        let gain = match self.rolloff {
+            Rolloff::None => 1.0,
+            Rolloff::Linear => {
+                let range = self.max_distance - self.reference_distance;
+                if range > 0.0 {
+                    1.0 - self.rolloff_factor * (distance - self.reference_distance) / range
+                } else {
+                    1.0
+                }
+            }
+            Rolloff::Inverse => {
+                self.reference_distance / (self.reference_distance + self.rolloff_factor * (distance - self.reference_distance))
+            }
+            Rolloff::Exponential => (distance / self.reference_distance).powf(-self.rolloff_factor),
+        };
+        gain.clamp(0.0, 1.0)
+    }
+
+}
+
+impl Default for Attenuation {
+
+    fn default() -> Self {
+        Self::new(Rolloff::Inverse, 100.0, 1000.0)
+    }
+
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct SpatialGains {
+    pub distance: f32,
+    pub left: f32,
+    pub right: f32,
+}
+
+impl Default for SpatialGains {
+
+    fn default() -> Self {
+        Self {
+            distance: 1.0,
+            left: 1.0,
+            right: 1.0,
+        }
+    }
+
+}
+
+pub(crate) fn spatial_gains(listener: Position, emitter: Position, attenuation: &Attenuation) -> SpatialGains {
+    let delta_x = emitter.x - listener.x;
+    let delta_y = emitter.y - listener.y;
+    let distance = (delta_x * delta_x + delta_y * delta_y).sqrt();
+    let pan = (delta_x / distance.max(attenuation.reference_distance)).clamp(-1.0, 1.0);
+    // equal-power pan scaled by sqrt(2) so a centered sound keeps unity gain
+    let angle = (pan + 1.0) * std::f32::consts::FRAC_PI_4;
+    SpatialGains {
+        distance: attenuation.gain(distance),
+        left: (angle.cos() * std::f32::consts::SQRT_2).min(1.0),
+        right: (angle.sin() * std::f32::consts::SQRT_2).min(1.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Attenuation, Rolloff, spatial_gains};
+    use crate::math::Position;
+
+    #[test]
+    fn rolloff() {
+        let linear = Attenuation::new(Rolloff::Linear, 10.0, 110.0);
+        assert_eq!(linear.gain(0.0), 1.0);
+        assert_eq!(linear.gain(60.0), 0.5);
+        assert_eq!(linear.gain(500.0), 0.0);
+
+        let inverse = Attenuation::new(Rolloff::Inverse, 10.0, 1000.0);
+        assert_eq!(inverse.gain(10.0), 1.0);
+        assert_eq!(inverse.gain(20.0), 0.5);
+
+        let exponential = Attenuation::new(Rolloff::Exponential, 10.0, 1000.0).rolloff_factor(2.0);
+        assert_eq!(exponential.gain(20.0), 0.25);
+
+        let none = Attenuation::new(Rolloff::None, 10.0, 1000.0);
+        assert_eq!(none.gain(999.0), 1.0);
+    }
+
+    #[test]
+    fn pan() {
+        let attenuation = Attenuation::new(Rolloff::None, 10.0, 1000.0);
+        let listener = Position::new(100.0, 100.0);
+
+        let gains = spatial_gains(listener, Position::new(100.0, 100.0), &attenuation);
+        assert!((gains.left - 1.0).abs() < 1e-6);
+        assert!((gains.right - 1.0).abs() < 1e-6);
+
+        let gains = spatial_gains(listener, Position::new(0.0, 100.0), &attenuation);
+        assert!((gains.left - 1.0).abs() < 1e-6);
+        assert!(gains.right.abs() < 1e-6);
+
+        let gains = spatial_gains(listener, Position::new(200.0, 100.0), &attenuation);
+        assert!(gains.left.abs() < 1e-6);
+        assert!((gains.right - 1.0).abs() < 1e-6);
+
+        let gains = spatial_gains(listener, Position::new(105.0, 100.0), &attenuation);
+        assert!(gains.left <= 1.0 && gains.right <= 1.0);
+        assert!(gains.right > gains.left);
+
+        let attenuation = Attenuation::new(Rolloff::Inverse, 10.0, 1000.0);
+        let gains = spatial_gains(listener, Position::new(100.0, 120.0), &attenuation);
+        assert_eq!(gains.distance, 0.5);
+    }
+
+}
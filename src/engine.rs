@@ -1,10 +1,10 @@
 use crate::error::{GameError, GameResult};
 use crate::math::Delta;
-use crate::event::{Event, KeyAction};
+use crate::event::{Event, UserEvent, KeyAction};
 use crate::filesystem::{Filesystem, FilesystemConfig};
 use crate::window::{Window, WindowConfig, LogicalPosition, LogicalSize};
 use crate::graphics::{Graphics, GraphicsConfig};
-use crate::timer::{Timer, TimerConfig};
+use crate::timer::{Timer, TimerConfig, FramePacing};
 use crate::keyboard::{Keyboard, KeyboardConfig};
 use crate::mouse::{Mouse, MouseConfig};
 use crate::touch::{Touch, TouchConfig};
@@ -12,9 +12,12 @@ use crate::touchpad::{Touchpad, TouchpadConfig};
 use crate::gamepad::{Gamepad, GamepadConfig};
 use crate::audio::{Audio, AudioConfig};
 use crate::game::Game;
-use winit::event_loop::{EventLoop, ControlFlow};
+use winit::event_loop::{EventLoop, EventLoopProxy, ControlFlow};
 use winit::event::{StartCause, WindowEvent, MouseScrollDelta};
 use winit::platform::desktop::EventLoopExtDesktop;
+use std::any::Any;
+use std::sync::mpsc::{self, Sender, Receiver};
+use std::time::{Instant, Duration};
 
 #[derive(Debug)]
 enum State {
@@ -24,8 +27,35 @@ enum State {
     Broken(Option<GameError>),
 }
 
+#[derive(Debug, Clone)]
+enum ProxySender {
+    EventLoop(EventLoopProxy<UserEvent>),
+    Channel(Sender<UserEvent>),
+}
+
+#[derive(Debug, Clone)]
+pub struct EngineProxy {
+    sender: ProxySender,
+}
+
+impl EngineProxy {
+
+    pub fn send_event<T: Any + Send + Sync>(&self, event: T) -> GameResult {
+        let event = UserEvent::new(event);
+        match &self.sender {
+            ProxySender::EventLoop(event_loop_proxy) => event_loop_proxy.send_event(event)
+                .map_err(|error| GameError::RuntimeError(Box::new(error))),
+            ProxySender::Channel(sender) => sender.send(event)
+                .map_err(|error| GameError::RuntimeError(Box::new(error))),
+        }
+    }
+
+}
+
 pub struct Engine {
-    event_loop: Option<EventLoop<()>>,
+    event_loop: Option<EventLoop<UserEvent>>,
+    proxy: EngineProxy,
+    user_events: Option<Receiver<UserEvent>>,
     filesystem: Filesystem,
     window: Window,
     graphics: Graphics,
@@ -41,6 +71,14 @@ pub struct Engine {
 
 impl Engine {
 
+    pub fn proxy(&self) -> EngineProxy {
+        self.proxy.clone()
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.window.is_headless()
+    }
+
     pub fn filesystem(&mut self) -> &mut Filesystem {
         &mut self.filesystem
     }
@@ -95,25 +133,61 @@ impl Engine {
         }
     }
 
-    fn handle_event(&mut self, event: winit::event::Event<()>, control_flow: &mut ControlFlow, game: &mut impl Game) -> GameResult {
+    fn clear_input_states(&mut self) {
+        self.keyboard.clear_states();
+        self.mouse.clear_states();
+        self.touch.clear_states();
+        self.touchpad.clear_states();
+        self.gamepad.clear_states();
+    }
+
+    fn frame(&mut self, game: &mut impl Game) -> GameResult {
+        let update_instant = Instant::now();
+        let mut updated = false;
+        while self.timer.next_update() {
+            if updated {
+                self.clear_input_states();
+            }
+            game.update(self)?;
+            updated = true;
+        }
+        let render_instant = Instant::now();
+        game.render(self)?;
+        let present_instant = Instant::now();
+        self.graphics.present()?;
+        self.timer.record_frame(
+            render_instant.duration_since(update_instant),
+            present_instant.duration_since(render_instant),
+            present_instant.elapsed(),
+        );
+        if updated {
+            self.clear_input_states();
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: winit::event::Event<UserEvent>, control_flow: &mut ControlFlow, game: &mut impl Game) -> GameResult {
         match event {
             winit::event::Event::NewEvents(start_cause) => {
                 match start_cause {
-                    StartCause::Init => self.timer.reset_tick(),
+                    StartCause::Init => {
+                        self.timer.reset_tick();
+                        game.on_start(self)?;
+                    }
                     _ => (),
                 }
             }
             winit::event::Event::WindowEvent { window_id, event } => {
-                if window_id == self.window.window().id() {
+                if Some(window_id) == self.window.id() {
                     match event {
                         WindowEvent::CloseRequested => {
-                            if !game.event(self, Event::WindowClose)? {
+                            if !game.event(self, Event::WindowClose)? && game.on_quit_requested(self)? {
                                 *control_flow = ControlFlow::Exit;
                                 self.quit();
                             }
                         }
                         WindowEvent::Resized(physical_size) => {
-                            let scale_factor = self.window.window().scale_factor();
+                            let scale_factor = self.window.context().scale_factor();
                             let logical_size = physical_size.to_logical(scale_factor);
                             self.graphics.resize(physical_size, scale_factor);
                             game.event(self, Event::WindowResize(LogicalSize::new(logical_size.width, logical_size.height)))?;
@@ -124,7 +198,7 @@ impl Engine {
                             game.event(self, Event::WindowResize(LogicalSize::new(logical_size.width, logical_size.height)))?;
                         }
                         WindowEvent::Moved(physical_position) => {
-                            let scale_factor = self.window.window().scale_factor();
+                            let scale_factor = self.window.context().scale_factor();
                             let logical_position = physical_position.to_logical(scale_factor);
                             game.event(self, Event::WindowMove(LogicalPosition::new(logical_position.x, logical_position.y)))?;
                         }
@@ -147,7 +221,7 @@ impl Engine {
                             game.event(self, Event::ModifiersChange(state))?;
                         }
                         WindowEvent::CursorMoved { position, .. } => {
-                            let scale_factor = self.window.window().scale_factor();
+                            let scale_factor = self.window.context().scale_factor();
                             let logical_position = position.to_logical(scale_factor);
                             let position = LogicalPosition::new(logical_position.x, logical_position.y);
                             self.mouse.handle_move_event(position);
@@ -185,7 +259,7 @@ impl Engine {
                             let id = touch.id;
                             let phase = touch.phase.into();
                             let position = {
-                                let scale_factor = self.window.window().scale_factor();
+                                let scale_factor = self.window.context().scale_factor();
                                 let logical_position = touch.location.to_logical(scale_factor);
                                 LogicalPosition::new(logical_position.x, logical_position.y)
                             };
@@ -203,12 +277,17 @@ impl Engine {
             }
             winit::event::Event::Suspended => {
                 game.event(self, Event::AppSuspend)?;
+                game.on_suspend(self)?;
                 self.audio.suspend();
             }
             winit::event::Event::Resumed => {
                 self.audio.resume();
+                game.on_resume(self)?;
                 game.event(self, Event::AppResume)?;
             }
+            winit::event::Event::UserEvent(event) => {
+                game.event(self, Event::User(event))?;
+            }
             winit::event::Event::MainEventsCleared => {
                 let events = self.gamepad.pump_events();
                 for event in events {
@@ -248,32 +327,92 @@ impl Engine {
                         _ => (),
                     }
                 }
-                self.window.window().request_redraw();
+                if *control_flow != ControlFlow::Exit {
+                    match self.timer.frame_pacing(Instant::now()) {
+                        FramePacing::Ready => {
+                            self.window.request_redraw();
+                            *control_flow = ControlFlow::Poll;
+                        }
+                        FramePacing::Spin => *control_flow = ControlFlow::Poll,
+                        FramePacing::WaitUntil(instant) => *control_flow = ControlFlow::WaitUntil(instant),
+                    }
+                }
             }
             winit::event::Event::RedrawRequested(window_id) => {
-                if window_id == self.window.window().id() {
+                if Some(window_id) == self.window.id() {
                     if self.timer.tick_and_check() {
-                        game.update(self)?;
-                        game.render(self)?;
-                        self.graphics.present()?;
-                        self.keyboard.clear_states();
-                        self.mouse.clear_states();
-                        self.touch.clear_states();
-                        self.touchpad.clear_states();
-                        self.gamepad.clear_states();
+                        self.frame(game)?;
                     }
                 }
             }
-            winit::event::Event::LoopDestroyed => {
-                self.quit();
-                self.graphics.clean();
-            }
             _ => (),
         }
         Ok(())
     }
 
+    fn shutdown(&mut self, game: &mut impl Game) {
+        self.quit();
+        if let Err(error) = game.on_shutdown(self) {
+            match &self.state {
+                State::Broken(_) => (),
+                _ => self.state = State::Broken(Some(error)),
+            }
+        }
+        self.graphics.clean();
+    }
+
+    pub fn step(&mut self, game: &mut impl Game, delta_time: Duration) -> GameResult {
+        let user_events = self.user_events.take()
+            .ok_or_else(|| GameError::StateError("engine can only be stepped in headless mode".into()))?;
+        let result = self.step_with_events(game, delta_time, &user_events);
+        self.user_events = Some(user_events);
+        if result.is_err() {
+            // like the windowed loop, a game error breaks the engine and shuts it down
+            if let State::Running = self.state {
+                self.state = State::Broken(None);
+                self.shutdown(game);
+            }
+        }
+        result
+    }
+
+    fn step_with_events(&mut self, game: &mut impl Game, delta_time: Duration, user_events: &Receiver<UserEvent>) -> GameResult {
+        match &self.state {
+            State::Ready => {
+                self.state = State::Running;
+                self.timer.reset_tick();
+                game.on_start(self)?;
+            }
+            State::Running => (),
+            _ => return Err(GameError::StateError(format!("engine can not be stepped on state `{:?}`", self.state).into())),
+        }
+        while let Ok(event) = user_events.try_recv() {
+            game.event(self, Event::User(event))?;
+        }
+        self.timer.step(delta_time);
+        self.frame(game)
+    }
+
+    pub fn finish(&mut self, game: &mut impl Game) -> GameResult {
+        if !self.is_headless() {
+            return Err(GameError::StateError("engine can only be finished manually in headless mode".into()));
+        }
+        match &self.state {
+            State::Ready => self.quit(),
+            State::Running => self.shutdown(game),
+            _ => (),
+        }
+        match &mut self.state {
+            State::Broken(error) => Err(error.take()
+                .unwrap_or_else(|| GameError::StateError("engine is broken, the error was returned by `step`".into()))),
+            _ => Ok(()),
+        }
+    }
+
     pub fn run(&mut self, game: &mut impl Game) -> GameResult {
+        if self.is_headless() {
+            return Err(GameError::StateError("engine can not be run in headless mode, use `step` instead".into()));
+        }
         match &self.state {
             State::Ready => self.state = State::Running,
             _ => return Err(GameError::StateError(format!("engine can not be run on state `{:?}`", self.state).into())),
@@ -282,6 +421,10 @@ impl Engine {
         let mut event_loop = self.event_loop.take()
             .ok_or_else(|| GameError::RuntimeError("no event_loop instance".into()))?;
         event_loop.run_return(|event, _, control_flow| {
+            if let winit::event::Event::LoopDestroyed = event {
+                self.shutdown(game);
+                return;
+            }
             match &self.state {
                 State::Finished | State::Broken(_) => *control_flow = ControlFlow::Exit,
                 State::Running => {
@@ -328,6 +471,7 @@ pub struct EngineBuilder {
     touchpad_config: Option<TouchpadConfig>,
     gamepad_config: Option<GamepadConfig>,
     audio_config: Option<AudioConfig>,
+    headless: bool,
 }
 
 impl EngineBuilder {
@@ -344,6 +488,7 @@ impl EngineBuilder {
             touchpad_config: None,
             gamepad_config: None,
             audio_config: None,
+            headless: false,
         }
     }
 
@@ -397,6 +542,11 @@ impl EngineBuilder {
         self
     }
 
+    pub fn headless(mut self, headless: bool) -> Self {
+        self.headless = headless;
+        self
+    }
+
     pub fn build(self) -> GameResult<Engine> {
         let filesystem_config = self.filesystem_config.unwrap_or_else(|| FilesystemConfig::new());
         let window_config = self.window_config.unwrap_or_else(|| WindowConfig::new());
@@ -409,21 +559,31 @@ impl EngineBuilder {
         let gamepad_config = self.gamepad_config.unwrap_or_else(|| GamepadConfig::new());
         let audio_config = self.audio_config.unwrap_or_else(|| AudioConfig::new());
 
-        let event_loop = EventLoop::new();
-
         let filesystem = Filesystem::new(filesystem_config)?;
-        let window = Window::new(window_config, &event_loop, &filesystem)?;
-        let graphics = Graphics::new(graphics_config, window.context_wrapper().clone())?;
+        let (event_loop, proxy, user_events, window) = if self.headless {
+            let (sender, receiver) = mpsc::channel();
+            let proxy = EngineProxy { sender: ProxySender::Channel(sender) };
+            let window = Window::new_headless(window_config)?;
+            (None, proxy, Some(receiver), window)
+        } else {
+            let event_loop = EventLoop::with_user_event();
+            let proxy = EngineProxy { sender: ProxySender::EventLoop(event_loop.create_proxy()) };
+            let window = Window::new(window_config, &event_loop, &filesystem)?;
+            (Some(event_loop), proxy, None, window)
+        };
+        let graphics = Graphics::new(graphics_config, window.context().clone())?;
         let timer = Timer::new(timer_config)?;
         let keyboard = Keyboard::new(keyboard_config)?;
-        let mouse = Mouse::new(mouse_config, window.context_wrapper().clone())?;
+        let mouse = Mouse::new(mouse_config, window.context().clone())?;
         let touch = Touch::new(touch_config)?;
         let touchpad = Touchpad::new(touchpad_config)?;
         let gamepad = Gamepad::new(gamepad_config)?;
         let audio = Audio::new(audio_config)?;
 
         Ok(Engine {
-            event_loop: Some(event_loop),
+            event_loop,
+            proxy,
+            user_events,
             filesystem,
             window,
             graphics,
@@ -5,6 +5,9 @@ use crate::mouse::MouseButton;
 use crate::touch::TouchPhase;
 use crate::gamepad::{GamepadButton, GamepadAxis, GamepadId};
 use winit::event::ElementState;
+use std::any::Any;
+use std::sync::Arc;
+use std::fmt;
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
 pub(crate) enum KeyState {
@@ -42,6 +45,49 @@ impl Into<KeyState> for KeyAction {
 
 }
 
+#[derive(Clone)]
+pub struct UserEvent {
+    payload: Arc<dyn Any + Send + Sync>,
+}
+
+impl UserEvent {
+
+    pub fn new<T: Any + Send + Sync>(payload: T) -> Self {
+        Self {
+            payload: Arc::new(payload),
+        }
+    }
+
+    pub fn is<T: Any + Send + Sync>(&self) -> bool {
+        self.payload.is::<T>()
+    }
+
+    pub fn downcast_ref<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.payload.downcast_ref::<T>()
+    }
+
+    pub fn downcast<T: Any + Send + Sync>(self) -> Result<Arc<T>, Self> {
+        self.payload.downcast::<T>().map_err(|payload| Self { payload })
+    }
+
+}
+
+impl fmt::Debug for UserEvent {
+
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("UserEvent { .. }")
+    }
+
+}
+
+impl PartialEq for UserEvent {
+
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.payload, &other.payload)
+    }
+
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Event {
     AppSuspend,
@@ -95,4 +141,24 @@ pub enum Event {
         axis: GamepadAxis,
         value: f32,
     },
+    User(UserEvent),
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::UserEvent;
+
+    #[test]
+    fn user_event() {
+        let event = UserEvent::new(String::from("loaded"));
+        assert!(event.is::<String>());
+        assert!(!event.is::<u32>());
+        assert_eq!(event.downcast_ref::<String>().map(|text| text.as_str()), Some("loaded"));
+        assert_eq!(event.clone(), event);
+        assert_ne!(UserEvent::new(1), UserEvent::new(1));
+        let event = event.downcast::<u32>().unwrap_err();
+        assert_eq!(*event.downcast::<String>().unwrap(), "loaded");
+    }
+
 }
@@ -1,31 +1,555 @@
+mod virtual_path;
+mod metadata;
+mod mount;
+mod directory;
+mod archive;
+mod embedded;
+
+use mount::Mount;
+use directory::DirectoryMount;
+use archive::ArchiveMount;
+use embedded::EmbeddedMount;
+
+pub use metadata::{FileType, Metadata};
+pub use embedded::EmbeddedAssets;
+
 use crate::error::{GameError, GameResult};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+use std::fs::{File, OpenOptions};
 
-pub struct Filesystem {}
+enum MountSource {
+    Native(PathBuf),
+    Embedded(String),
+}
+
+struct MountEntry {
+    source: MountSource,
+    mount_point: String,
+    mount: Box<dyn Mount>,
+}
+
+pub struct Filesystem {
+    resource_root: PathBuf,
+    save_dir: Option<PathBuf>,
+    mounts: Vec<MountEntry>,
+}
 
 impl Filesystem {
 
-    pub(crate) fn new(_: FilesystemConfig) -> GameResult<Self> {
-        Ok(Self {})
+    pub(crate) fn new(filesystem_config: FilesystemConfig) -> GameResult<Self> {
+        let resource_root = match filesystem_config.asset_root {
+            Some(asset_root) => base_dir().join(asset_root),
+            None => base_dir(),
+        };
+        let save_dir = match filesystem_config.save_dir {
+            Some(save_dir) => Some(resource_root.join(save_dir)),
+            None => match filesystem_config.identity {
+                Some(identity) => Some(user_data_dir()
+                    .ok_or_else(|| GameError::InitError("no user data directory for identity".into()))?
+                    .join(identity)),
+                None => None,
+            },
+        };
+        let mut filesystem = Self {
+            resource_root: resource_root.clone(),
+            save_dir: save_dir.clone(),
+            mounts: Vec::new(),
+        };
+        if let Some(save_dir) = save_dir {
+            std::fs::create_dir_all(&save_dir).map_err(|error| GameError::InitError(Box::new(error)))?;
+            filesystem.mount(save_dir, "", true)?;
+        }
+        filesystem.mount(resource_root, "", true)?;
+        for mount_config in filesystem_config.mounts {
+            match mount_config {
+                MountConfig::Native { source, mount_point } => filesystem.mount(source, mount_point, true)?,
+                MountConfig::Embedded { name, assets, mount_point } => filesystem.mount_embedded(name, assets, mount_point, true)?,
+            }
+        }
+        Ok(filesystem)
+    }
+
+    pub fn resource_root(&self) -> &Path {
+        &self.resource_root
+    }
+
+    pub fn save_dir(&self) -> Option<&Path> {
+        self.save_dir.as_deref()
+    }
+
+    pub fn mount(&mut self, source: impl AsRef<Path>, mount_point: impl AsRef<Path>, append: bool) -> GameResult {
+        let source = self.resource_root.join(source);
+        let mount_point = virtual_path::normalize(mount_point.as_ref())?;
+        let mount: Box<dyn Mount> = if source.is_dir() {
+            Box::new(DirectoryMount::new(&source))
+        } else if source.is_file() {
+            Box::new(ArchiveMount::open(&source)?)
+        } else {
+            return Err(GameError::IoError(Box::new(io::Error::new(io::ErrorKind::NotFound, format!("mount source not found: {}", source.display())))));
+        };
+        self.add_mount(MountEntry {
+            source: MountSource::Native(source),
+            mount_point,
+            mount,
+        }, append);
+        Ok(())
+    }
+
+    pub fn mount_embedded(&mut self, name: impl Into<String>, assets: EmbeddedAssets, mount_point: impl AsRef<Path>, append: bool) -> GameResult {
+        let mount_point = virtual_path::normalize(mount_point.as_ref())?;
+        self.add_mount(MountEntry {
+            source: MountSource::Embedded(name.into()),
+            mount_point,
+            mount: Box::new(EmbeddedMount::new(assets)),
+        }, append);
+        Ok(())
+    }
+
+    fn add_mount(&mut self, entry: MountEntry, append: bool) {
+        if append {
+            self.mounts.push(entry);
+        } else {
+            self.mounts.insert(0, entry);
+        }
+    }
+
+    pub fn unmount(&mut self, source: impl AsRef<Path>) -> bool {
+        let source = self.resource_root.join(source);
+        self.remove_mounts(|mount_source| match mount_source {
+            MountSource::Native(native_source) => *native_source == source,
+            MountSource::Embedded(_) => false,
+        })
+    }
+
+    pub fn unmount_embedded(&mut self, name: &str) -> bool {
+        self.remove_mounts(|mount_source| match mount_source {
+            MountSource::Native(_) => false,
+            MountSource::Embedded(embedded_name) => embedded_name == name,
+        })
+    }
+
+    fn remove_mounts(&mut self, predicate: impl Fn(&MountSource) -> bool) -> bool {
+        let count = self.mounts.len();
+        self.mounts.retain(|entry| !predicate(&entry.source));
+        self.mounts.len() != count
+    }
+
+    pub fn mounts(&self) -> Vec<(&Path, &str)> {
+        self.mounts.iter()
+            .map(|entry| {
+                let source = match &entry.source {
+                    MountSource::Native(source) => source.as_path(),
+                    MountSource::Embedded(name) => Path::new(name),
+                };
+                (source, entry.mount_point.as_str())
+            })
+            .collect()
+    }
+
+    fn is_mount_parent(&self, path: &str) -> bool {
+        path.is_empty() || self.mounts.iter().any(|entry| {
+            match virtual_path::strip_prefix(&entry.mount_point, path) {
+                Some(rest) => !rest.is_empty(),
+                None => false,
+            }
+        })
+    }
+
+    pub fn exists(&self, path: impl AsRef<Path>) -> bool {
+        let path = match virtual_path::normalize(path.as_ref()) {
+            Ok(path) => path,
+            Err(_) => return false,
+        };
+        self.is_mount_parent(&path) || self.mounts.iter().any(|entry| {
+            match virtual_path::strip_prefix(&path, &entry.mount_point) {
+                Some(relative_path) => entry.mount.exists(relative_path),
+                None => false,
+            }
+        })
+    }
+
+    pub fn metadata(&self, path: impl AsRef<Path>) -> GameResult<Metadata> {
+        let path = virtual_path::normalize(path.as_ref())?;
+        for entry in &self.mounts {
+            if let Some(relative_path) = virtual_path::strip_prefix(&path, &entry.mount_point) {
+                if entry.mount.exists(relative_path) {
+                    return entry.mount.metadata(relative_path);
+                }
+            }
+        }
+        if self.is_mount_parent(&path) {
+            Ok(Metadata::directory())
+        } else {
+            Err(mount::not_found_error(&path))
+        }
+    }
+
+    pub fn list_dir(&self, path: impl AsRef<Path>) -> GameResult<Vec<PathBuf>> {
+        let path = virtual_path::normalize(path.as_ref())?;
+        let mut found = false;
+        let mut names = BTreeSet::new();
+        for entry in &self.mounts {
+            if let Some(relative_path) = virtual_path::strip_prefix(&path, &entry.mount_point) {
+                if entry.mount.exists(relative_path) && entry.mount.metadata(relative_path)?.is_dir() {
+                    found = true;
+                    names.extend(entry.mount.list_dir(relative_path)?);
+                }
+            } else if let Some(rest) = virtual_path::strip_prefix(&entry.mount_point, &path) {
+                if let Some(name) = rest.split('/').next() {
+                    found = true;
+                    names.insert(name.to_owned());
+                }
+            }
+        }
+        if !found {
+            return Err(mount::not_found_error(&path));
+        }
+        Ok(names.iter().map(|name| PathBuf::from(virtual_path::join(&path, name))).collect())
     }
 
     pub fn read(&self, path: impl AsRef<Path>) -> GameResult<Vec<u8>> {
-        std::fs::read(path).map_err(|error| GameError::IoError(Box::new(error)))
+        let path = virtual_path::normalize(path.as_ref())?;
+        for entry in &self.mounts {
+            if let Some(relative_path) = virtual_path::strip_prefix(&path, &entry.mount_point) {
+                if entry.mount.exists(relative_path) && entry.mount.metadata(relative_path)?.is_file() {
+                    return entry.mount.read(relative_path);
+                }
+            }
+        }
+        Err(mount::not_found_error(&path))
     }
 
     pub fn read_to_string(&self, path: impl AsRef<Path>) -> GameResult<String> {
-        std::fs::read_to_string(path).map_err(|error| GameError::IoError(Box::new(error)))
+        let bytes = self.read(path)?;
+        String::from_utf8(bytes).map_err(|error| GameError::IoError(Box::new(error)))
+    }
+
+    fn save_path(&self, path: &Path) -> GameResult<PathBuf> {
+        let save_dir = self.save_dir.as_ref()
+            .ok_or_else(|| GameError::StateError("no save directory, set an identity in filesystem config".into()))?;
+        let path = virtual_path::normalize(path)?;
+        if path.is_empty() {
+            return Err(GameError::IoError(Box::new(io::Error::new(io::ErrorKind::InvalidInput, "path must not be empty"))));
+        }
+        Ok(virtual_path::to_native(save_dir, &path))
+    }
+
+    pub fn write(&mut self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> GameResult {
+        let path = self.save_path(path.as_ref())?;
+        let file_name = path.file_name()
+            .and_then(|file_name| file_name.to_str())
+            .ok_or_else(|| GameError::IoError(Box::new(io::Error::new(io::ErrorKind::InvalidInput, "illegal file name"))))?;
+        let temp_path = path.with_file_name(directory::temp_file_name(file_name));
+        let result = File::create(&temp_path)
+            .and_then(|mut file| {
+                file.write_all(contents.as_ref())?;
+                file.sync_all()
+            })
+            .and_then(|_| std::fs::rename(&temp_path, &path));
+        if let Err(error) = result {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(GameError::IoError(Box::new(error)));
+        }
+        Ok(())
     }
 
+    pub fn append(&mut self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> GameResult {
+        let path = self.save_path(path.as_ref())?;
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(contents.as_ref()))
+            .map_err(|error| GameError::IoError(Box::new(error)))
+    }
+
+    pub fn create_dir(&mut self, path: impl AsRef<Path>) -> GameResult {
+        let path = self.save_path(path.as_ref())?;
+        std::fs::create_dir_all(path).map_err(|error| GameError::IoError(Box::new(error)))
+    }
+
+    pub fn remove(&mut self, path: impl AsRef<Path>) -> GameResult {
+        let path = self.save_path(path.as_ref())?;
+        let result = if path.is_dir() {
+            std::fs::remove_dir(path)
+        } else {
+            std::fs::remove_file(path)
+        };
+        result.map_err(|error| GameError::IoError(Box::new(error)))
+    }
+
+}
+
+fn base_dir() -> PathBuf {
+    // `cargo run` sets the manifest dir of the running game, whose assets are not next to the exe
+    if let Some(manifest_dir) = std::env::var_os("CARGO_MANIFEST_DIR") {
+        return PathBuf::from(manifest_dir);
+    }
+    std::env::current_exe().ok()
+        .and_then(|exe_path| exe_path.parent().map(|exe_dir| exe_dir.to_path_buf()))
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "windows")]
+fn user_data_dir() -> Option<PathBuf> {
+    std::env::var_os("APPDATA").map(PathBuf::from)
+}
+
+#[cfg(target_os = "macos")]
+fn user_data_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn user_data_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|data_home| data_home.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
 }
 
 #[derive(Debug, Clone)]
-pub struct FilesystemConfig {}
+enum MountConfig {
+    Native {
+        source: PathBuf,
+        mount_point: PathBuf,
+    },
+    Embedded {
+        name: String,
+        assets: EmbeddedAssets,
+        mount_point: PathBuf,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct FilesystemConfig {
+    asset_root: Option<PathBuf>,
+    identity: Option<String>,
+    save_dir: Option<PathBuf>,
+    mounts: Vec<MountConfig>,
+}
 
 impl FilesystemConfig {
 
     pub fn new() -> Self {
-        Self {}
+        Self {
+            asset_root: None,
+            identity: None,
+            save_dir: None,
+            mounts: Vec::new(),
+        }
+    }
+
+    pub fn asset_root(mut self, asset_root: impl Into<PathBuf>) -> Self {
+        self.asset_root = Some(asset_root.into());
+        self
+    }
+
+    pub fn identity(mut self, identity: impl Into<String>) -> Self {
+        let identity = identity.into();
+        assert!(!identity.is_empty(), "identity must not be empty");
+        assert!(!identity.contains(&['/', '\\'][..]), "identity must not contain path separator");
+        self.identity = Some(identity);
+        self
+    }
+
+    pub fn save_dir(mut self, save_dir: impl Into<PathBuf>) -> Self {
+        self.save_dir = Some(save_dir.into());
+        self
+    }
+
+    pub fn mount(mut self, source: impl Into<PathBuf>, mount_point: impl Into<PathBuf>) -> Self {
+        self.mounts.push(MountConfig::Native {
+            source: source.into(),
+            mount_point: mount_point.into(),
+        });
+        self
+    }
+
+    pub fn mount_embedded(mut self, name: impl Into<String>, assets: EmbeddedAssets, mount_point: impl Into<PathBuf>) -> Self {
+        self.mounts.push(MountConfig::Embedded {
+            name: name.into(),
+            assets,
+            mount_point: mount_point.into(),
+        });
+        self
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Filesystem, FilesystemConfig, EmbeddedAssets};
+    use std::path::{Path, PathBuf};
+    use std::io::Write;
+
+    fn create_test_dir(name: &str) -> PathBuf {
+        let test_dir = std::env::temp_dir().join(format!("tge-filesystem-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&test_dir);
+        std::fs::create_dir_all(test_dir.join("assets/sprites")).unwrap();
+        std::fs::create_dir_all(test_dir.join("patch")).unwrap();
+        std::fs::write(test_dir.join("assets/ferris.png"), b"ferris").unwrap();
+        std::fs::write(test_dir.join("assets/sprites/coin.png"), b"coin").unwrap();
+        std::fs::write(test_dir.join("patch/ferris.png"), b"patched ferris").unwrap();
+        test_dir
+    }
+
+    #[test]
+    fn mount() {
+        let test_dir = create_test_dir("mount");
+        let mut filesystem = Filesystem::new(FilesystemConfig::new()
+            .asset_root(&test_dir)
+            .mount("assets/sprites", "data/sprites")).unwrap();
+        assert_eq!(filesystem.read("assets/ferris.png").unwrap(), b"ferris");
+        assert_eq!(filesystem.read("/assets/./ferris.png").unwrap(), b"ferris");
+        assert_eq!(filesystem.read_to_string("data/sprites/coin.png").unwrap(), "coin");
+        assert!(filesystem.read("assets").is_err());
+        assert!(filesystem.read("../ferris.png").is_err());
+
+        filesystem.mount("patch", "assets", false).unwrap();
+        assert_eq!(filesystem.read("assets/ferris.png").unwrap(), b"patched ferris");
+        assert!(filesystem.unmount("patch"));
+        assert!(!filesystem.unmount("patch"));
+        assert_eq!(filesystem.read("assets/ferris.png").unwrap(), b"ferris");
+
+        std::fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn query() {
+        let test_dir = create_test_dir("query");
+        let filesystem = Filesystem::new(FilesystemConfig::new()
+            .asset_root(&test_dir)
+            .mount("assets/sprites", "data/sprites")).unwrap();
+        assert!(filesystem.exists("assets/ferris.png"));
+        assert!(filesystem.exists("data"));
+        assert!(!filesystem.exists("assets/missing.png"));
+
+        let metadata = filesystem.metadata("assets/ferris.png").unwrap();
+        assert!(metadata.is_file());
+        assert_eq!(metadata.len(), 6);
+        assert!(filesystem.metadata("data").unwrap().is_dir());
+        assert!(filesystem.metadata("assets/missing.png").is_err());
+
+        assert_eq!(filesystem.list_dir("assets").unwrap(), vec![Path::new("assets/ferris.png"), Path::new("assets/sprites")]);
+        assert_eq!(filesystem.list_dir("data").unwrap(), vec![Path::new("data/sprites")]);
+        assert!(filesystem.list_dir("").unwrap().contains(&PathBuf::from("data")));
+        assert!(filesystem.list_dir("assets/missing").is_err());
+
+        std::fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn archive() {
+        let test_dir = create_test_dir("archive");
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(test_dir.join("data.zip")).unwrap());
+        writer.start_file("assets/ferris.png", zip::write::FileOptions::default()).unwrap();
+        writer.write_all(b"packed ferris").unwrap();
+        writer.start_file("assets/fonts/mono.ttf", zip::write::FileOptions::default()).unwrap();
+        writer.write_all(b"mono").unwrap();
+        writer.finish().unwrap();
+
+        let mut filesystem = Filesystem::new(FilesystemConfig::new().asset_root(&test_dir)).unwrap();
+        filesystem.mount("data.zip", "", false).unwrap();
+        assert_eq!(filesystem.read("assets/ferris.png").unwrap(), b"packed ferris");
+        assert_eq!(filesystem.read("assets/sprites/coin.png").unwrap(), b"coin");
+        assert!(filesystem.metadata("assets/fonts").unwrap().is_dir());
+        assert_eq!(filesystem.metadata("assets/fonts/mono.ttf").unwrap().len(), 4);
+        assert_eq!(filesystem.list_dir("assets").unwrap(), vec![
+            Path::new("assets/ferris.png"),
+            Path::new("assets/fonts"),
+            Path::new("assets/sprites"),
+        ]);
+
+        filesystem.mount("data.zip", "packed", true).unwrap();
+        assert_eq!(filesystem.read_to_string("packed/assets/fonts/mono.ttf").unwrap(), "mono");
+        assert!(filesystem.unmount("data.zip"));
+        assert_eq!(filesystem.read("assets/ferris.png").unwrap(), b"ferris");
+        assert!(!filesystem.exists("packed/assets"));
+
+        std::fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn embedded() {
+        let test_dir = create_test_dir("embedded");
+        let assets = EmbeddedAssets::new()
+            .file("ferris.png", b"embedded ferris")
+            .files(vec![("/fonts/mono.ttf", &b"mono"[..]), ("fonts/sans.ttf", &b"sans"[..])]);
+        assert_eq!(assets.len(), 3);
+        let mut filesystem = Filesystem::new(FilesystemConfig::new()
+            .asset_root(&test_dir)
+            .mount_embedded("builtin", assets, "assets")).unwrap();
+        assert_eq!(filesystem.read("assets/ferris.png").unwrap(), b"ferris");
+        assert_eq!(filesystem.read_to_string("assets/fonts/sans.ttf").unwrap(), "sans");
+        assert!(filesystem.metadata("assets/fonts").unwrap().is_dir());
+        assert_eq!(filesystem.list_dir("assets/fonts").unwrap(), vec![
+            Path::new("assets/fonts/mono.ttf"),
+            Path::new("assets/fonts/sans.ttf"),
+        ]);
+
+        filesystem.mount_embedded("override", EmbeddedAssets::new().file("ferris.png", b"override ferris"), "assets", false).unwrap();
+        assert_eq!(filesystem.read("assets/ferris.png").unwrap(), b"override ferris");
+        assert!(filesystem.unmount_embedded("override"));
+        assert!(filesystem.unmount_embedded("builtin"));
+        assert!(!filesystem.exists("assets/fonts"));
+
+        filesystem.mount_embedded("patch", EmbeddedAssets::new().file("ferris.png", b"embedded patch"), "patched", true).unwrap();
+        filesystem.mount("patch", "patched", true).unwrap();
+        assert!(!filesystem.unmount("builtin"));
+        assert!(filesystem.unmount_embedded("patch"));
+        assert_eq!(filesystem.read("patched/ferris.png").unwrap(), b"patched ferris");
+        assert!(filesystem.unmount("patch"));
+        assert!(!filesystem.exists("patched"));
+
+        std::fs::remove_dir_all(test_dir).unwrap();
+    }
+
+    #[test]
+    fn write() {
+        let test_dir = create_test_dir("write");
+        let mut filesystem = Filesystem::new(FilesystemConfig::new()
+            .asset_root(&test_dir)
+            .save_dir(test_dir.join("save"))).unwrap();
+        assert_eq!(filesystem.save_dir(), Some(test_dir.join("save").as_path()));
+
+        filesystem.write("assets/ferris.png", b"saved ferris").unwrap_err();
+        filesystem.create_dir("assets").unwrap();
+        filesystem.write("assets/ferris.png", b"saved ferris").unwrap();
+        assert_eq!(filesystem.read("assets/ferris.png").unwrap(), b"saved ferris");
+        filesystem.write("assets/ferris.png", b"ferris 2").unwrap();
+        assert_eq!(filesystem.read("assets/ferris.png").unwrap(), b"ferris 2");
+        std::fs::write(test_dir.join("save/assets/.coin.png.tmp"), b"partial coin").unwrap();
+        assert_eq!(filesystem.list_dir("assets").unwrap(), vec![Path::new("assets/ferris.png"), Path::new("assets/sprites")]);
+        std::fs::remove_file(test_dir.join("save/assets/.coin.png.tmp")).unwrap();
+
+        filesystem.append("log.txt", "a").unwrap();
+        filesystem.append("log.txt", "b").unwrap();
+        assert_eq!(filesystem.read_to_string("log.txt").unwrap(), "ab");
+        assert!(filesystem.write("../escape.txt", "escape").is_err());
+
+        filesystem.remove("assets/ferris.png").unwrap();
+        filesystem.remove("assets").unwrap();
+        assert_eq!(filesystem.read("assets/ferris.png").unwrap(), b"ferris");
+        assert!(filesystem.remove("assets").is_err());
+
+        let mut filesystem = Filesystem::new(FilesystemConfig::new()
+            .asset_root(&test_dir)
+            .save_dir("relative_save")).unwrap();
+        assert_eq!(filesystem.save_dir(), Some(test_dir.join("relative_save").as_path()));
+        filesystem.write("log.txt", "relative").unwrap();
+        assert_eq!(filesystem.read_to_string("log.txt").unwrap(), "relative");
+        assert!(test_dir.join("relative_save/log.txt").is_file());
+
+        let mut filesystem = Filesystem::new(FilesystemConfig::new().asset_root(&test_dir)).unwrap();
+        assert!(filesystem.save_dir().is_none());
+        assert!(filesystem.write("log.txt", "a").is_err());
+
+        std::fs::remove_dir_all(test_dir).unwrap();
     }
 
 }
@@ -0,0 +1,83 @@
+use super::{Metadata, FileType};
+use super::mount::{self, Mount};
+use super::virtual_path;
+use crate::error::{GameError, GameResult};
+use zip::ZipArchive;
+use std::path::Path;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+struct ArchiveEntry {
+    index: usize,
+    len: u64,
+}
+
+pub struct ArchiveMount {
+    archive: RefCell<ZipArchive<BufReader<File>>>,
+    files: HashMap<String, ArchiveEntry>,
+    dirs: HashSet<String>,
+}
+
+impl ArchiveMount {
+
+    pub fn open(path: impl AsRef<Path>) -> GameResult<Self> {
+        let file = File::open(path).map_err(|error| GameError::IoError(Box::new(error)))?;
+        let mut archive = ZipArchive::new(BufReader::new(file))
+            .map_err(|error| GameError::IoError(Box::new(error)))?;
+        let mut files = HashMap::new();
+        let mut dirs = HashSet::new();
+        dirs.insert(String::new());
+        for index in 0..archive.len() {
+            let file = archive.by_index(index).map_err(|error| GameError::IoError(Box::new(error)))?;
+            let name = match virtual_path::normalize(Path::new(file.name())) {
+                Ok(name) if !name.is_empty() => name,
+                _ => continue,
+            };
+            mount::insert_parent_dirs(&mut dirs, &name);
+            if file.is_dir() {
+                dirs.insert(name);
+            } else {
+                files.insert(name, ArchiveEntry { index, len: file.size() });
+            }
+        }
+        Ok(Self {
+            archive: RefCell::new(archive),
+            files,
+            dirs,
+        })
+    }
+
+}
+
+impl Mount for ArchiveMount {
+
+    fn exists(&self, path: &str) -> bool {
+        self.files.contains_key(path) || self.dirs.contains(path)
+    }
+
+    fn metadata(&self, path: &str) -> GameResult<Metadata> {
+        if let Some(entry) = self.files.get(path) {
+            Ok(Metadata::new(FileType::File, entry.len, None))
+        } else if self.dirs.contains(path) {
+            Ok(Metadata::directory())
+        } else {
+            Err(mount::not_found_error(path))
+        }
+    }
+
+    fn read(&self, path: &str) -> GameResult<Vec<u8>> {
+        let entry = self.files.get(path).ok_or_else(|| mount::not_found_error(path))?;
+        let mut archive = self.archive.borrow_mut();
+        let mut file = archive.by_index(entry.index).map_err(|error| GameError::IoError(Box::new(error)))?;
+        let mut bytes = Vec::with_capacity(entry.len as usize);
+        file.read_to_end(&mut bytes).map_err(|error| GameError::IoError(Box::new(error)))?;
+        Ok(bytes)
+    }
+
+    fn list_dir(&self, path: &str) -> GameResult<Vec<String>> {
+        mount::list_dir(self.files.keys(), &self.dirs, path)
+    }
+
+}
@@ -0,0 +1,64 @@
+use super::Metadata;
+use super::mount::Mount;
+use super::virtual_path;
+use crate::error::{GameError, GameResult};
+use std::path::PathBuf;
+
+pub struct DirectoryMount {
+    root: PathBuf,
+}
+
+impl DirectoryMount {
+
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+        }
+    }
+
+    fn native_path(&self, path: &str) -> PathBuf {
+        virtual_path::to_native(&self.root, path)
+    }
+
+}
+
+impl Mount for DirectoryMount {
+
+    fn exists(&self, path: &str) -> bool {
+        self.native_path(path).exists()
+    }
+
+    fn metadata(&self, path: &str) -> GameResult<Metadata> {
+        let metadata = std::fs::metadata(self.native_path(path))
+            .map_err(|error| GameError::IoError(Box::new(error)))?;
+        Ok(metadata.into())
+    }
+
+    fn read(&self, path: &str) -> GameResult<Vec<u8>> {
+        std::fs::read(self.native_path(path)).map_err(|error| GameError::IoError(Box::new(error)))
+    }
+
+    fn list_dir(&self, path: &str) -> GameResult<Vec<String>> {
+        let entries = std::fs::read_dir(self.native_path(path))
+            .map_err(|error| GameError::IoError(Box::new(error)))?;
+        let mut names = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|error| GameError::IoError(Box::new(error)))?;
+            if let Some(name) = entry.file_name().to_str() {
+                if !is_temp_file_name(name) {
+                    names.push(name.to_owned());
+                }
+            }
+        }
+        Ok(names)
+    }
+
+}
+
+pub fn temp_file_name(file_name: &str) -> String {
+    format!(".{}.tmp", file_name)
+}
+
+fn is_temp_file_name(name: &str) -> bool {
+    name.len() > ".tmp".len() + 1 && name.starts_with('.') && name.ends_with(".tmp")
+}
@@ -0,0 +1,98 @@
+use super::{Metadata, FileType};
+use super::mount::{self, Mount};
+use super::virtual_path;
+use crate::error::GameResult;
+use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Clone, Default)]
+pub struct EmbeddedAssets {
+    files: HashMap<String, &'static [u8]>,
+}
+
+impl EmbeddedAssets {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn file(mut self, path: impl AsRef<Path>, bytes: &'static [u8]) -> Self {
+        let path = virtual_path::normalize(path.as_ref()).expect("illegal embedded asset path");
+        assert!(!path.is_empty(), "embedded asset path must not be empty");
+        self.files.insert(path, bytes);
+        self
+    }
+
+    pub fn files<P: AsRef<Path>>(self, files: impl IntoIterator<Item = (P, &'static [u8])>) -> Self {
+        files.into_iter().fold(self, |assets, (path, bytes)| assets.file(path, bytes))
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+}
+
+impl fmt::Debug for EmbeddedAssets {
+
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("EmbeddedAssets")
+            .field("files", &self.files.keys().collect::<Vec<_>>())
+            .finish()
+    }
+
+}
+
+pub struct EmbeddedMount {
+    files: HashMap<String, &'static [u8]>,
+    dirs: HashSet<String>,
+}
+
+impl EmbeddedMount {
+
+    pub fn new(assets: EmbeddedAssets) -> Self {
+        let mut dirs = HashSet::new();
+        dirs.insert(String::new());
+        for path in assets.files.keys() {
+            mount::insert_parent_dirs(&mut dirs, path);
+        }
+        Self {
+            files: assets.files,
+            dirs,
+        }
+    }
+
+}
+
+impl Mount for EmbeddedMount {
+
+    fn exists(&self, path: &str) -> bool {
+        self.files.contains_key(path) || self.dirs.contains(path)
+    }
+
+    fn metadata(&self, path: &str) -> GameResult<Metadata> {
+        if let Some(bytes) = self.files.get(path) {
+            Ok(Metadata::new(FileType::File, bytes.len() as u64, None))
+        } else if self.dirs.contains(path) {
+            Ok(Metadata::directory())
+        } else {
+            Err(mount::not_found_error(path))
+        }
+    }
+
+    fn read(&self, path: &str) -> GameResult<Vec<u8>> {
+        self.files.get(path)
+            .map(|bytes| bytes.to_vec())
+            .ok_or_else(|| mount::not_found_error(path))
+    }
+
+    fn list_dir(&self, path: &str) -> GameResult<Vec<String>> {
+        mount::list_dir(self.files.keys(), &self.dirs, path)
+    }
+
+}
@@ -0,0 +1,67 @@
+use std::time::SystemTime;
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum FileType {
+    File,
+    Directory,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Metadata {
+    file_type: FileType,
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl Metadata {
+
+    pub(crate) fn new(file_type: FileType, len: u64, modified: Option<SystemTime>) -> Self {
+        Self {
+            file_type,
+            len,
+            modified,
+        }
+    }
+
+    pub(crate) fn directory() -> Self {
+        Self::new(FileType::Directory, 0, None)
+    }
+
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    pub fn is_file(&self) -> bool {
+        self.file_type == FileType::File
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.file_type == FileType::Directory
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
+
+}
+
+impl From<std::fs::Metadata> for Metadata {
+
+    fn from(metadata: std::fs::Metadata) -> Self {
+        let file_type = if metadata.is_dir() {
+            FileType::Directory
+        } else {
+            FileType::File
+        };
+        Self::new(file_type, metadata.len(), metadata.modified().ok())
+    }
+
+}
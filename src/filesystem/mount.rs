@@ -0,0 +1,47 @@
+use super::Metadata;
+use super::virtual_path;
+use crate::error::{GameError, GameResult};
+use std::io;
+use std::collections::HashSet;
+
+pub trait Mount {
+
+    fn exists(&self, path: &str) -> bool;
+
+    fn metadata(&self, path: &str) -> GameResult<Metadata>;
+
+    fn read(&self, path: &str) -> GameResult<Vec<u8>>;
+
+    fn list_dir(&self, path: &str) -> GameResult<Vec<String>>;
+
+}
+
+pub fn not_found_error(path: &str) -> GameError {
+    GameError::IoError(Box::new(io::Error::new(io::ErrorKind::NotFound, format!("file not found: {}", path))))
+}
+
+pub fn insert_parent_dirs(dirs: &mut HashSet<String>, path: &str) {
+    let mut parent = path;
+    while let Some(separator) = parent.rfind('/') {
+        parent = &parent[..separator];
+        dirs.insert(parent.to_owned());
+    }
+}
+
+pub fn list_dir<'a>(files: impl Iterator<Item = &'a String>, dirs: &'a HashSet<String>, path: &str) -> GameResult<Vec<String>> {
+    if !dirs.contains(path) {
+        return Err(not_found_error(path));
+    }
+    let names = files
+        .chain(dirs.iter())
+        .filter_map(|name| {
+            let name = virtual_path::strip_prefix(name, path)?;
+            if name.is_empty() || name.contains('/') {
+                None
+            } else {
+                Some(name.to_owned())
+            }
+        })
+        .collect();
+    Ok(names)
+}
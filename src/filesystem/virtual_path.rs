@@ -0,0 +1,88 @@
+use crate::error::{GameError, GameResult};
+use std::path::{Path, PathBuf, Component};
+use std::io;
+
+pub fn normalize(path: &Path) -> GameResult<String> {
+    let mut components = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) => {
+                return Err(GameError::IoError(Box::new(io::Error::new(io::ErrorKind::InvalidInput, format!("path must not have a prefix: {}", path.display())))));
+            }
+            Component::RootDir | Component::CurDir => (),
+            Component::ParentDir => {
+                if components.pop().is_none() {
+                    return Err(GameError::IoError(Box::new(io::Error::new(io::ErrorKind::InvalidInput, format!("path is outside of filesystem: {}", path.display())))));
+                }
+            }
+            Component::Normal(name) => {
+                let name = name.to_str()
+                    .ok_or_else(|| GameError::IoError(Box::new(io::Error::new(io::ErrorKind::InvalidInput, format!("path is not valid unicode: {}", path.display())))))?;
+                components.push(name);
+            }
+        }
+    }
+    Ok(components.join("/"))
+}
+
+pub fn join(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_owned()
+    } else if name.is_empty() {
+        parent.to_owned()
+    } else {
+        format!("{}/{}", parent, name)
+    }
+}
+
+pub fn strip_prefix<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    if prefix.is_empty() {
+        return Some(path);
+    }
+    if path == prefix {
+        return Some("");
+    }
+    if path.starts_with(prefix) && path[prefix.len()..].starts_with('/') {
+        Some(&path[prefix.len() + 1..])
+    } else {
+        None
+    }
+}
+
+pub fn to_native(root: &Path, path: &str) -> PathBuf {
+    let mut native_path = root.to_path_buf();
+    if !path.is_empty() {
+        native_path.extend(path.split('/'));
+    }
+    native_path
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{normalize, join, strip_prefix};
+    use std::path::Path;
+
+    #[test]
+    fn normalize_path() {
+        assert_eq!(normalize(Path::new("assets/ferris.png")).unwrap(), "assets/ferris.png");
+        assert_eq!(normalize(Path::new("/assets/./sprites/../ferris.png")).unwrap(), "assets/ferris.png");
+        assert_eq!(normalize(Path::new("")).unwrap(), "");
+        assert!(normalize(Path::new("../ferris.png")).is_err());
+        if cfg!(windows) {
+            assert!(normalize(Path::new("C:\\assets\\ferris.png")).is_err());
+            assert!(normalize(Path::new("\\\\server\\share\\ferris.png")).is_err());
+        }
+    }
+
+    #[test]
+    fn prefix() {
+        assert_eq!(join("", "assets"), "assets");
+        assert_eq!(join("assets", "ferris.png"), "assets/ferris.png");
+        assert_eq!(strip_prefix("assets/ferris.png", ""), Some("assets/ferris.png"));
+        assert_eq!(strip_prefix("assets/ferris.png", "assets"), Some("ferris.png"));
+        assert_eq!(strip_prefix("assets", "assets"), Some(""));
+        assert_eq!(strip_prefix("assets2/ferris.png", "assets"), None);
+    }
+
+}
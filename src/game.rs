@@ -8,6 +8,26 @@ pub trait Game {
 
     fn render(&mut self, engine: &mut Engine) -> GameResult;
 
+    fn on_start(&mut self, _: &mut Engine) -> GameResult {
+        Ok(())
+    }
+
+    fn on_quit_requested(&mut self, _: &mut Engine) -> GameResult<bool> {
+        Ok(true)
+    }
+
+    fn on_suspend(&mut self, _: &mut Engine) -> GameResult {
+        Ok(())
+    }
+
+    fn on_resume(&mut self, _: &mut Engine) -> GameResult {
+        Ok(())
+    }
+
+    fn on_shutdown(&mut self, _: &mut Engine) -> GameResult {
+        Ok(())
+    }
+
     fn event(&mut self, _: &mut Engine, _: Event) -> GameResult<bool> {
         Ok(false)
     }
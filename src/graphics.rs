@@ -8,29 +8,45 @@ mod renderer;
 mod image;
 mod texture;
 mod canvas;
+mod glyph_cache;
+mod font;
 mod sprite_params;
+mod text_params;
+mod text_layout;
+mod shape_params;
+mod shape;
+mod path;
+mod camera;
 
 use opengl::BufferUsage;
 use renderer::{Renderer, RendererBuilder};
 use texture::TextureHolder;
+use shape::{ShapeMesh, StrokeStyle};
+use glyph_cache::CachedGlyph;
+use text_layout::DEFAULT_TEXT_SIZE;
 
-pub use opengl::{PrimitiveType, FilterMode, Filter, WrapMode, Wrap};
+pub use opengl::{PrimitiveType, FilterMode, Filter, WrapMode, Wrap, BlendEquation, BlendFactor, BlendComponent, BlendMode};
 pub use program::Program;
 pub use color::Color;
 pub use vertex::Vertex;
-pub use self::image::Image;
+pub use self::image::{Image, ImageFormat};
 pub(crate) use self::image::validate_pixels;
 pub use texture::{Texture, NO_TEXTURE};
 pub use canvas::{Canvas, NO_CANVAS};
+pub use font::{Font, LineMetrics};
 pub use sprite_params::SpriteDrawParams;
+pub use text_params::TextDrawParams;
+pub use text_layout::{TextAlign, TextSpan, TextLayout, PositionedGlyph};
+pub use shape_params::{DrawMode, LineJoin, LineCap, FillRule, ShapeDrawParams};
+pub use path::Path;
+pub use camera::Camera2D;
 
 use crate::error::{GameError, GameResult};
-use crate::math::{Position, Point, Size, Region, Viewport};
-use winit::window::Window;
+use crate::math::{Position, Point, Vector, Scale, Size, Region, Viewport, Angle};
+use crate::window::WindowContext;
 use winit::dpi::{LogicalPosition, LogicalSize, PhysicalSize};
-use glutin::{ContextWrapper, PossiblyCurrent};
 use glow::{Context, HasContext};
-use glam::{Vec3, Vec4, Quat, Mat4};
+use glam::{Vec2, Vec3, Vec4, Quat, Mat4};
 use std::rc::Rc;
 
 const SPRITE_VERTEX_COUNT: usize = 4;
@@ -44,10 +60,11 @@ const SPRITE_ELEMENTS: [u32; SPRITE_ELEMENT_COUNT] = [
 struct DrawCommand {
     pub texture: Rc<opengl::Texture>,
     pub primitive: PrimitiveType,
+    pub blend_mode: BlendMode,
 }
 
 pub struct Graphics {
-    context_wrapper: Rc<ContextWrapper<PossiblyCurrent, Window>>,
+    context: Rc<WindowContext>,
     gl: Rc<Context>,
     size: Size,
     viewport: Viewport,
@@ -58,6 +75,10 @@ pub struct Graphics {
     default_wrap: Wrap,
     default_texture: Rc<opengl::Texture>,
     canvas: Option<Rc<opengl::Framebuffer>>,
+    blend_mode: BlendMode,
+    transform: Mat4,
+    transform_stack: Vec<Mat4>,
+    presented: bool,
     renderer: Renderer,
     vertices: Vec<Vertex>,
     elements: Vec<u32>,
@@ -66,12 +87,12 @@ pub struct Graphics {
 
 impl Graphics {
 
-    pub(crate) fn new(graphics_config: GraphicsConfig, context_wrapper: Rc<ContextWrapper<PossiblyCurrent, Window>>) -> GameResult<Self> {
-        let gl = Context::from_loader_function(|symbol| context_wrapper.get_proc_address(symbol).cast());
+    pub(crate) fn new(graphics_config: GraphicsConfig, context: Rc<WindowContext>) -> GameResult<Self> {
+        let gl = Context::from_loader_function(|symbol| context.get_proc_address(symbol).cast());
         let gl = Rc::new(gl);
 
-        let physical_size = context_wrapper.window().inner_size();
-        let scale_factor = context_wrapper.window().scale_factor();
+        let physical_size = context.inner_size();
+        let scale_factor = context.scale_factor();
         let logical_size = physical_size.to_logical(scale_factor);
         let size = Size::new(logical_size.width, logical_size.height);
         let viewport = Viewport::new(0.0, 0.0, logical_size.width, logical_size.height);
@@ -97,15 +118,15 @@ impl Graphics {
         let draw_command = DrawCommand {
             texture: default_texture.clone(),
             primitive: PrimitiveType::Triangles,
+            blend_mode: BlendMode::default(),
         };
 
         unsafe {
             gl.enable(glow::BLEND);
-            gl.blend_func(glow::SRC_ALPHA, glow::ONE_MINUS_SRC_ALPHA);
         }
 
         Ok(Self {
-            context_wrapper,
+            context,
             gl,
             size,
             viewport,
@@ -116,6 +137,10 @@ impl Graphics {
             default_wrap: graphics_config.default_wrap,
             default_texture,
             canvas: None,
+            blend_mode: BlendMode::default(),
+            transform: Mat4::identity(),
+            transform_stack: Vec::new(),
+            presented: false,
             renderer,
             vertices,
             elements,
@@ -123,16 +148,12 @@ impl Graphics {
         })
     }
 
-    fn window(&self) -> &Window {
-        self.context_wrapper.window()
-    }
-
     pub(crate) fn gl(&self) -> &Rc<Context> {
         &self.gl
     }
 
     pub(crate) fn resize(&mut self, physical_size: PhysicalSize<u32>, scale_factor: f64) {
-        self.context_wrapper.resize(physical_size);
+        self.context.resize(physical_size);
         if self.canvas.is_none() {
             let logical_size = physical_size.to_logical(scale_factor);
             self.size.set(logical_size.width, logical_size.height);
@@ -149,6 +170,7 @@ impl Graphics {
         if !self.vertices.is_empty() && !self.elements.is_empty() {
             self.renderer.update_vertices(0, &self.vertices);
             self.renderer.update_elements(0, &self.elements).expect("renderer update elements error");
+            self.draw_command.blend_mode.apply(&self.gl);
             self.draw_command.texture.bind();
             self.renderer.draw_elements(self.draw_command.primitive, self.elements.len(), 0);
             self.draw_command.texture.unbind();
@@ -159,7 +181,10 @@ impl Graphics {
 
     pub(crate) fn present(&mut self) -> GameResult {
         self.flush();
-        self.context_wrapper.swap_buffers()
+        self.transform = Mat4::identity();
+        self.transform_stack.clear();
+        self.presented = true;
+        self.context.swap_buffers()
             .map_err(|error| GameError::RuntimeError(Box::new(error)))
     }
 
@@ -196,7 +221,7 @@ impl Graphics {
                 }
                 self.projection_matrix = Mat4::orthographic_rh_gl(0.0, self.viewport.width, 0.0, self.viewport.height, -1.0, 1.0);
             } else {
-                let scale_factor = self.window().scale_factor();
+                let scale_factor = self.context.scale_factor();
                 let physical_viewport = {
                     let physical_position = LogicalPosition::new(self.viewport.x, self.viewport.y).to_physical::<i32>(scale_factor);
                     let physical_size = LogicalSize::new(self.viewport.width, self.viewport.height).to_physical::<i32>(scale_factor);
@@ -271,8 +296,8 @@ impl Graphics {
                 }
                 self.projection_matrix = Mat4::orthographic_rh_gl(0.0, self.size.width, 0.0, self.size.height, -1.0, 1.0);
             } else {
-                let physical_size = self.window().inner_size();
-                let scale_factor = self.window().scale_factor();
+                let physical_size = self.context.inner_size();
+                let scale_factor = self.context.scale_factor();
                 let logical_size = physical_size.to_logical(scale_factor);
                 self.size.set(logical_size.width, logical_size.height);
                 self.viewport.set(0.0, 0.0, logical_size.width, logical_size.height);
@@ -285,7 +310,122 @@ impl Graphics {
         }
     }
 
+    pub fn screenshot(&mut self) -> GameResult<Image> {
+        // the back buffer is undefined after swapping until the next frame draws into it
+        if self.presented {
+            return Err(GameError::StateError("screenshot must be taken before the frame is presented, take it in `Game::render`".into()));
+        }
+        self.flush();
+        if let Some(canvas) = &self.canvas {
+            canvas.unbind();
+        }
+        let physical_size = self.context.inner_size();
+        let size = Size::new(physical_size.width, physical_size.height);
+        let mut pixels = self.read_pixels(size);
+        if let Some(canvas) = &self.canvas {
+            canvas.bind();
+        }
+        // the default framebuffer is stored bottom-up
+        flip_rows(size, &mut pixels);
+        Image::new(size, pixels)
+    }
+
+    pub(crate) fn read_canvas(&mut self, canvas: &Canvas) -> GameResult<Image> {
+        self.flush();
+        canvas.framebuffer().bind();
+        let size = canvas.size();
+        // canvases are drawn with a flipped projection, so the rows are already top-down
+        let pixels = self.read_pixels(size);
+        match &self.canvas {
+            Some(canvas) => canvas.bind(),
+            None => canvas.framebuffer().unbind(),
+        }
+        Image::new(size, pixels)
+    }
+
+    fn read_pixels(&self, size: Size<u32>) -> Vec<u8> {
+        let mut pixels = vec![0; (size.width * size.height * 4) as usize];
+        unsafe {
+            self.gl.pixel_store_i32(glow::PACK_ALIGNMENT, 1);
+            self.gl.read_pixels(
+                0,
+                0,
+                size.width as i32,
+                size.height as i32,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                &mut pixels,
+            );
+        }
+        pixels
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    pub fn push_transform(&mut self) {
+        self.transform_stack.push(self.transform);
+    }
+
+    pub fn pop_transform(&mut self) {
+        self.transform = self.transform_stack.pop().expect("no pushed transform to pop");
+    }
+
+    pub fn reset_transform(&mut self) {
+        self.transform = Mat4::identity();
+    }
+
+    pub fn translate(&mut self, offset: impl Into<Vector>) {
+        let offset = offset.into();
+        self.transform = self.transform * Mat4::from_translation(Vec3::new(offset.x, offset.y, 0.0));
+    }
+
+    pub fn rotate(&mut self, angle: Angle) {
+        self.transform = self.transform * Mat4::from_rotation_z(angle.radians_value());
+    }
+
+    pub fn scale(&mut self, scale: impl Into<Scale>) {
+        let scale = scale.into();
+        self.transform = self.transform * Mat4::from_scale(Vec3::new(scale.x, scale.y, 1.0));
+    }
+
+    pub fn shear(&mut self, shear: impl Into<Vector>) {
+        let shear = shear.into();
+        let shear_matrix = Mat4::from_cols(
+            Vec4::new(1.0, shear.y, 0.0, 0.0),
+            Vec4::new(shear.x, 1.0, 0.0, 0.0),
+            Vec4::new(0.0, 0.0, 1.0, 0.0),
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+        );
+        self.transform = self.transform * shear_matrix;
+    }
+
+    pub fn push_camera(&mut self, camera: &Camera2D) {
+        self.push_transform();
+        self.transform = self.transform * camera.view_matrix(self.viewport.size());
+    }
+
+    pub fn transform_point(&self, point: impl Into<Position>) -> Position {
+        let point = point.into();
+        let point = self.transform * Vec4::new(point.x, point.y, 0.0, 1.0);
+        Position::new(point.x(), point.y())
+    }
+
+    pub fn inverse_transform_point(&self, point: impl Into<Position>) -> Position {
+        let point = point.into();
+        let point = self.transform.inverse() * Vec4::new(point.x, point.y, 0.0, 1.0);
+        Position::new(point.x(), point.y())
+    }
+
     pub fn clear(&mut self, color: impl Into<Color>) {
+        if self.canvas.is_none() {
+            self.presented = false;
+        }
         let color = color.into();
         unsafe {
             self.gl.clear_color(color.red, color.green, color.blue, color.alpha);
@@ -300,7 +440,17 @@ impl Graphics {
         }
     }
 
-    fn append_vertices_and_elements(&mut self, vertices: Vec<Vertex>, elements: Option<Vec<u32>>) {
+    fn append_vertices_and_elements(&mut self, mut vertices: Vec<Vertex>, elements: Option<Vec<u32>>) {
+        if self.canvas.is_none() {
+            self.presented = false;
+        }
+        if self.transform != Mat4::identity() {
+            for vertex in &mut vertices {
+                let position = self.transform * Vec4::new(vertex.position.x, vertex.position.y, 0.0, 1.0);
+                vertex.position.set(position.x(), position.y());
+            }
+        }
+
         let mut elements = elements.unwrap_or_else(|| {
             let mut elements = Vec::with_capacity(vertices.len());
             for i in 0..vertices.len() as u32 {
@@ -334,6 +484,7 @@ impl Graphics {
         self.switch_draw_command(DrawCommand {
             texture,
             primitive,
+            blend_mode: self.blend_mode,
         });
 
         self.append_vertices_and_elements(vertices, elements);
@@ -351,6 +502,7 @@ impl Graphics {
         self.switch_draw_command(DrawCommand {
             texture,
             primitive: PrimitiveType::Triangles,
+            blend_mode: self.blend_mode,
         });
 
         let region = params.region.unwrap_or_else(|| Region::new(0.0, 0.0, texture_size.width, texture_size.height));
@@ -400,6 +552,245 @@ impl Graphics {
         self.append_vertices_and_elements(vertices, Some(elements));
     }
 
+    fn draw_shape_mesh(&mut self, mesh: ShapeMesh, params: &ShapeDrawParams) {
+        if mesh.is_empty() {
+            return;
+        }
+        let color = params.color.unwrap_or(Color::WHITE);
+        let vertices = mesh.positions.into_iter()
+            .map(|position| Vertex {
+                position: Position::new(position.x(), position.y()),
+                uv: Vector::zero(),
+                color,
+            })
+            .collect();
+        self.draw_mesh(NO_TEXTURE, PrimitiveType::Triangles, vertices, Some(mesh.elements));
+    }
+
+    fn draw_shape(&mut self, points: &[Vec2], closed: bool, params: &ShapeDrawParams) {
+        let mut mesh = ShapeMesh::new();
+        match params.mode.unwrap_or(DrawMode::Fill) {
+            DrawMode::Fill => shape::fill_convex(&mut mesh, points),
+            DrawMode::Stroke => stroke_points(&mut mesh, points, closed, params),
+        }
+        self.draw_shape_mesh(mesh, params);
+    }
+
+    pub fn draw_rect(&mut self, region: impl Into<Region>, params: ShapeDrawParams) {
+        let region = region.into();
+        let points = [
+            Vec2::new(region.left(), region.top()),
+            Vec2::new(region.right(), region.top()),
+            Vec2::new(region.right(), region.bottom()),
+            Vec2::new(region.left(), region.bottom()),
+        ];
+        self.draw_shape(&points, true, &params);
+    }
+
+    pub fn draw_circle(&mut self, center: impl Into<Position>, radius: f32, params: ShapeDrawParams) {
+        self.draw_ellipse(center, (radius, radius), params);
+    }
+
+    pub fn draw_ellipse(&mut self, center: impl Into<Position>, radius: impl Into<Vector>, params: ShapeDrawParams) {
+        let center = center.into();
+        let radius = radius.into();
+        let segments = params.segments.unwrap_or_else(|| shape::segments_for_radius(radius.x.max(radius.y)));
+        let points = shape::ellipse_points(Vec2::new(center.x, center.y), Vec2::new(radius.x, radius.y), segments);
+        self.draw_shape(&points, true, &params);
+    }
+
+    pub fn draw_arc(&mut self, center: impl Into<Position>, radius: f32, start: Angle, end: Angle, params: ShapeDrawParams) {
+        let center = center.into();
+        let center = Vec2::new(center.x, center.y);
+        let start = start.radians_value();
+        let sweep = end.radians_value() - start;
+        let segments = params.segments.unwrap_or_else(|| shape::segments_for_radius(radius));
+        let segments = shape::arc_segments(segments, sweep);
+        let mut points = shape::arc_points(center, Vec2::new(radius, radius), start, sweep, segments);
+        // a filled arc is a pie slice
+        if params.mode.unwrap_or(DrawMode::Fill) == DrawMode::Fill {
+            points.insert(0, center);
+        }
+        self.draw_shape(&points, false, &params);
+    }
+
+    pub fn draw_polygon(&mut self, points: &[Position], params: ShapeDrawParams) {
+        let points: Vec<Vec2> = points.iter().map(|point| Vec2::new(point.x, point.y)).collect();
+        let mut mesh = ShapeMesh::new();
+        match params.mode.unwrap_or(DrawMode::Fill) {
+            DrawMode::Fill => shape::fill_polygons(&mut mesh, &[&points[..]], params.fill_rule.unwrap_or(FillRule::NonZero)),
+            DrawMode::Stroke => stroke_points(&mut mesh, &points, true, &params),
+        }
+        self.draw_shape_mesh(mesh, &params);
+    }
+
+    pub fn draw_line(&mut self, from: impl Into<Position>, to: impl Into<Position>, params: ShapeDrawParams) {
+        let from = from.into();
+        let to = to.into();
+        self.draw_polyline(&[from, to], params);
+    }
+
+    pub fn draw_polyline(&mut self, points: &[Position], params: ShapeDrawParams) {
+        let points: Vec<Vec2> = points.iter().map(|point| Vec2::new(point.x, point.y)).collect();
+        let mut mesh = ShapeMesh::new();
+        stroke_points(&mut mesh, &points, false, &params);
+        self.draw_shape_mesh(mesh, &params);
+    }
+
+    pub fn draw_path(&mut self, path: &Path, params: ShapeDrawParams) {
+        let mut mesh = ShapeMesh::new();
+        match params.mode.unwrap_or(DrawMode::Fill) {
+            DrawMode::Fill => {
+                let polygons: Vec<&[Vec2]> = path.subpaths().iter().map(|subpath| &subpath.points[..]).collect();
+                shape::fill_polygons(&mut mesh, &polygons, params.fill_rule.unwrap_or(FillRule::NonZero));
+            }
+            DrawMode::Stroke => {
+                for subpath in path.subpaths() {
+                    stroke_points(&mut mesh, &subpath.points, subpath.closed, &params);
+                }
+            }
+        }
+        self.draw_shape_mesh(mesh, &params);
+    }
+
+    pub fn draw_text(&mut self, font: &Font, text: &str, params: TextDrawParams) -> GameResult {
+        // plain strings skip the full TextLayout: left aligned, no wrapping and a single size
+        let text_size = params.text_size.unwrap_or(DEFAULT_TEXT_SIZE);
+        if text.chars().any(|character| !font.is_glyph_cached(character, text_size)) {
+            self.flush();
+            let glyphs: Vec<(char, f32)> = text.chars().map(|character| (character, text_size)).collect();
+            font.cache_glyphs(&glyphs)?;
+        }
+
+        self.switch_draw_command(DrawCommand {
+            texture: font.cache_texture(),
+            primitive: PrimitiveType::Triangles,
+            blend_mode: self.blend_mode,
+        });
+
+        let (model_matrix, origin, tint) = text_transform(&params);
+        let cache_size = font.cache_texture_size();
+        let line_metrics = font.line_metrics(text_size);
+        let mut pen_x = 0.0;
+        let mut line_top = 0.0;
+        for character in text.chars() {
+            if character == '\n' {
+                pen_x = 0.0;
+                line_top += line_metrics.line_height();
+                continue;
+            }
+            if character.is_control() && character != '\t' {
+                continue;
+            }
+            let cached_glyph = font.cache_glyph(character, text_size)?;
+            if !character.is_whitespace() && cached_glyph.region.width > 0 && cached_glyph.region.height > 0 {
+                let x = pen_x + cached_glyph.offset_x - origin.x;
+                let y = line_top + line_metrics.ascent + cached_glyph.offset_y - origin.y;
+                self.append_glyph(&cached_glyph, x, y, tint, &model_matrix, cache_size);
+            }
+            pen_x += cached_glyph.advance_width;
+        }
+        Ok(())
+    }
+
+    pub fn draw_text_layout(&mut self, font: &Font, layout: &TextLayout, params: TextDrawParams) -> GameResult {
+        let (glyphs, _) = layout.layout(font);
+        if glyphs.iter().any(|glyph| !font.is_glyph_cached(glyph.character, glyph.size)) {
+            self.flush();
+            let keys: Vec<(char, f32)> = glyphs.iter().map(|glyph| (glyph.character, glyph.size)).collect();
+            font.cache_glyphs(&keys)?;
+        }
+
+        self.switch_draw_command(DrawCommand {
+            texture: font.cache_texture(),
+            primitive: PrimitiveType::Triangles,
+            blend_mode: self.blend_mode,
+        });
+
+        let (model_matrix, origin, tint) = text_transform(&params);
+        let cache_size = font.cache_texture_size();
+        for glyph in glyphs {
+            let cached_glyph = font.cache_glyph(glyph.character, glyph.size)?;
+            let color = Color::new(
+                glyph.color.red * tint.red,
+                glyph.color.green * tint.green,
+                glyph.color.blue * tint.blue,
+                glyph.color.alpha * tint.alpha,
+            );
+            self.append_glyph(&cached_glyph, glyph.region.x - origin.x, glyph.region.y - origin.y, color, &model_matrix, cache_size);
+        }
+        Ok(())
+    }
+
+    fn append_glyph(&mut self, cached_glyph: &CachedGlyph, x: f32, y: f32, color: Color, model_matrix: &Mat4, cache_size: Size<u32>) {
+        let width = cached_glyph.region.width as f32;
+        let height = cached_glyph.region.height as f32;
+        let x0y0 = *model_matrix * Vec4::new(x, y, 0.0, 1.0);
+        let x1y0 = *model_matrix * Vec4::new(x + width, y, 0.0, 1.0);
+        let x0y1 = *model_matrix * Vec4::new(x, y + height, 0.0, 1.0);
+        let x1y1 = *model_matrix * Vec4::new(x + width, y + height, 0.0, 1.0);
+        let uv = Region::new(
+            cached_glyph.region.x as f32 / cache_size.width as f32,
+            cached_glyph.region.y as f32 / cache_size.height as f32,
+            cached_glyph.region.width as f32 / cache_size.width as f32,
+            cached_glyph.region.height as f32 / cache_size.height as f32,
+        );
+        let vertices = vec![
+            Vertex {
+                position: Position::new(x0y0.x(), x0y0.y()),
+                uv: uv.top_left(),
+                color,
+            },
+            Vertex {
+                position: Position::new(x1y0.x(), x1y0.y()),
+                uv: uv.top_right(),
+                color,
+            },
+            Vertex {
+                position: Position::new(x0y1.x(), x0y1.y()),
+                uv: uv.bottom_left(),
+                color,
+            },
+            Vertex {
+                position: Position::new(x1y1.x(), x1y1.y()),
+                uv: uv.bottom_right(),
+                color,
+            },
+        ];
+        let elements = SPRITE_ELEMENTS.to_vec();
+        self.append_vertices_and_elements(vertices, Some(elements));
+    }
+
+}
+
+fn text_transform(params: &TextDrawParams) -> (Mat4, Point, Color) {
+    let origin = params.origin.unwrap_or_else(|| Point::zero());
+    let position = params.position.map(|position| Vec3::new(position.x, position.y, 0.0)).unwrap_or_else(|| Vec3::zero());
+    let rotation = params.rotation.map(|angle| Quat::from_rotation_z(angle.radians_value())).unwrap_or_else(|| Quat::from_rotation_z(0.0));
+    let scale = params.scale.map(|scale| Vec3::new(scale.x, scale.y, 1.0)).unwrap_or_else(|| Vec3::one());
+    let tint = params.color.unwrap_or(Color::WHITE);
+    (Mat4::from_scale_rotation_translation(scale, rotation, position), origin, tint)
+}
+
+fn stroke_points(mesh: &mut ShapeMesh, points: &[Vec2], closed: bool, params: &ShapeDrawParams) {
+    let style = StrokeStyle::new(params);
+    match &params.dash {
+        Some(dash) => {
+            for points in shape::dash(points, closed, dash, params.dash_offset.unwrap_or(0.0)) {
+                shape::stroke(mesh, &points, false, &style);
+            }
+        }
+        None => shape::stroke(mesh, points, closed, &style),
+    }
+}
+
+fn flip_rows(size: Size<u32>, pixels: &mut [u8]) {
+    let stride = size.width as usize * 4;
+    let height = size.height as usize;
+    for y in 0..height / 2 {
+        let (top, bottom) = pixels.split_at_mut((height - y - 1) * stride);
+        top[y * stride..(y + 1) * stride].swap_with_slice(&mut bottom[..stride]);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,198 @@
+use crate::math::{Position, Size, Region, Viewport, Angle};
+use glam::{Vec3, Mat4};
+
+#[derive(Debug, Copy, Clone)]
+pub struct Camera2D {
+    position: Position,
+    zoom: f32,
+    rotation: Angle,
+    bounds: Option<Region>,
+}
+
+impl Camera2D {
+
+    pub fn new() -> Self {
+        Self {
+            position: Position::zero(),
+            zoom: 1.0,
+            rotation: Angle::zero(),
+            bounds: None,
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: impl Into<Position>) {
+        self.position = position.into();
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    pub fn set_zoom(&mut self, zoom: f32) {
+        assert!(zoom > 0.0, "zoom must > 0.0");
+        self.zoom = zoom;
+    }
+
+    pub fn rotation(&self) -> Angle {
+        self.rotation
+    }
+
+    pub fn set_rotation(&mut self, rotation: Angle) {
+        self.rotation = rotation;
+    }
+
+    pub fn bounds(&self) -> Option<Region> {
+        self.bounds
+    }
+
+    pub fn set_bounds(&mut self, bounds: impl Into<Region>) {
+        self.bounds = Some(bounds.into());
+    }
+
+    pub fn clear_bounds(&mut self) {
+        self.bounds = None;
+    }
+
+    fn half_extents(&self, viewport_size: Size) -> (f32, f32) {
+        // a rotated camera sees the bounding box of its rotated viewport
+        let half_width = viewport_size.width / self.zoom / 2.0;
+        let half_height = viewport_size.height / self.zoom / 2.0;
+        let (sin, cos) = self.rotation.radians_value().sin_cos();
+        let (sin, cos) = (sin.abs(), cos.abs());
+        (half_width * cos + half_height * sin, half_width * sin + half_height * cos)
+    }
+
+    pub fn visible_position(&self, viewport_size: Size) -> Position {
+        match self.bounds {
+            Some(bounds) => {
+                let (half_width, half_height) = self.half_extents(viewport_size);
+                Position::new(
+                    clamp_axis(self.position.x, bounds.left() + half_width, bounds.right() - half_width),
+                    clamp_axis(self.position.y, bounds.top() + half_height, bounds.bottom() - half_height),
+                )
+            }
+            None => self.position,
+        }
+    }
+
+    pub fn visible_region(&self, viewport_size: Size) -> Region {
+        let position = self.visible_position(viewport_size);
+        let (half_width, half_height) = self.half_extents(viewport_size);
+        Region::new(position.x - half_width, position.y - half_height, half_width * 2.0, half_height * 2.0)
+    }
+
+    pub(crate) fn view_matrix(&self, viewport_size: Size) -> Mat4 {
+        let position = self.visible_position(viewport_size);
+        Mat4::from_translation(Vec3::new(viewport_size.width / 2.0, viewport_size.height / 2.0, 0.0))
+            * Mat4::from_scale(Vec3::new(self.zoom, self.zoom, 1.0))
+            * Mat4::from_rotation_z(-self.rotation.radians_value())
+            * Mat4::from_translation(Vec3::new(-position.x, -position.y, 0.0))
+    }
+
+    pub fn world_to_screen(&self, viewport: impl Into<Viewport>, point: impl Into<Position>) -> Position {
+        let viewport = viewport.into();
+        let point = point.into();
+        let position = self.visible_position(viewport.size());
+        let (sin, cos) = (-self.rotation.radians_value()).sin_cos();
+        let x = point.x - position.x;
+        let y = point.y - position.y;
+        Position::new(
+            (x * cos - y * sin) * self.zoom + viewport.x + viewport.width / 2.0,
+            (x * sin + y * cos) * self.zoom + viewport.y + viewport.height / 2.0,
+        )
+    }
+
+    pub fn screen_to_world(&self, viewport: impl Into<Viewport>, point: impl Into<Position>) -> Position {
+        let viewport = viewport.into();
+        let point = point.into();
+        let position = self.visible_position(viewport.size());
+        let (sin, cos) = self.rotation.radians_value().sin_cos();
+        let x = (point.x - viewport.x - viewport.width / 2.0) / self.zoom;
+        let y = (point.y - viewport.y - viewport.height / 2.0) / self.zoom;
+        Position::new(
+            x * cos - y * sin + position.x,
+            x * sin + y * cos + position.y,
+        )
+    }
+
+}
+
+impl Default for Camera2D {
+
+    fn default() -> Self {
+        Self::new()
+    }
+
+}
+
+fn clamp_axis(value: f32, min: f32, max: f32) -> f32 {
+    // center on the bounds when the visible area is larger than them
+    if min > max {
+        (min + max) / 2.0
+    } else {
+        value.clamp(min, max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: Position, b: Position) {
+        assert!((a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn convert() {
+        let viewport = Viewport::new(100.0, 50.0, 800.0, 600.0);
+        let mut camera = Camera2D::new();
+        assert_near(camera.world_to_screen(viewport, (0.0, 0.0)), Position::new(500.0, 350.0));
+
+        camera.set_position((10.0, 20.0));
+        camera.set_zoom(2.0);
+        assert_near(camera.world_to_screen(viewport, (15.0, 20.0)), Position::new(510.0, 350.0));
+        assert_near(camera.screen_to_world(viewport, (510.0, 350.0)), Position::new(15.0, 20.0));
+
+        camera.set_rotation(Angle::degrees(90.0));
+        assert_near(camera.world_to_screen(viewport, (15.0, 20.0)), Position::new(500.0, 340.0));
+        assert_near(camera.screen_to_world(viewport, (500.0, 340.0)), Position::new(15.0, 20.0));
+        let point = Position::new(-37.0, 81.5);
+        assert_near(camera.screen_to_world(viewport, camera.world_to_screen(viewport, point)), point);
+
+        let matrix = camera.view_matrix(viewport.size());
+        let local = matrix * glam::Vec4::new(15.0, 20.0, 0.0, 1.0);
+        assert_near(Position::new(local.x() + viewport.x, local.y() + viewport.y), Position::new(500.0, 340.0));
+    }
+
+    #[test]
+    fn bounds() {
+        let size = Size::new(800.0, 600.0);
+        let mut camera = Camera2D::new();
+        camera.set_bounds((0.0, 0.0, 2000.0, 1000.0));
+        assert_near(camera.visible_position(size), Position::new(400.0, 300.0));
+        camera.set_position((1900.0, 500.0));
+        assert_near(camera.visible_position(size), Position::new(1600.0, 500.0));
+        assert_eq!(camera.visible_region(size), Region::new(1200.0, 200.0, 800.0, 600.0));
+
+        camera.set_zoom(0.5);
+        assert_near(camera.visible_position(size), Position::new(1200.0, 500.0));
+        camera.set_zoom(0.1);
+        assert_near(camera.visible_position(size), Position::new(1000.0, 500.0));
+
+        camera.set_zoom(1.0);
+        camera.set_rotation(Angle::degrees(90.0));
+        camera.set_position((0.0, 0.0));
+        assert_near(camera.visible_position(size), Position::new(300.0, 400.0));
+        let region = camera.visible_region(size);
+        assert!((region.width - 600.0).abs() < 1e-3 && (region.height - 800.0).abs() < 1e-3);
+
+        camera.clear_bounds();
+        assert_eq!(camera.bounds(), None);
+        assert_near(camera.visible_position(size), Position::new(0.0, 0.0));
+    }
+
+}
@@ -1,4 +1,4 @@
-use super::{opengl, Filter, Wrap, Texture, TextureHolder};
+use super::{opengl, Filter, Wrap, Image, Texture, TextureHolder};
 use super::opengl::{Attachment, Framebuffer};
 use crate::error::{GameError, GameResult};
 use crate::math::Size;
@@ -34,6 +34,10 @@ impl Canvas {
         self.texture.size()
     }
 
+    pub fn to_image(&self, engine: &mut Engine) -> GameResult<Image> {
+        engine.graphics().read_canvas(self)
+    }
+
     pub fn filter(&self) -> Filter {
         self.texture.filter()
     }
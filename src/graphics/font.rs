@@ -0,0 +1,202 @@
+use super::glyph_cache::{GlyphCache, CachedGlyph};
+use super::opengl;
+use crate::error::{GameError, GameResult};
+use crate::math::Size;
+use crate::engine::Engine;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::path::Path;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LineMetrics {
+    pub ascent: f32,
+    pub descent: f32,
+    pub line_gap: f32,
+}
+
+impl LineMetrics {
+
+    pub fn line_height(&self) -> f32 {
+        self.ascent - self.descent + self.line_gap
+    }
+
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct GlyphMetrics {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub advance_width: f32,
+}
+
+pub struct Font {
+    font: fontdue::Font,
+    // line metrics for a size of 1.0
+    line_metrics: Option<LineMetrics>,
+    cache: RefCell<GlyphCache>,
+}
+
+impl Font {
+
+    pub fn from_bytes(engine: &mut Engine, bytes: &[u8]) -> GameResult<Self> {
+        let font = fontdue::Font::from_bytes(bytes, fontdue::FontSettings::default())
+            .map_err(|error| GameError::InitError(error.into()))?;
+        let line_metrics = read_line_metrics(bytes);
+        let cache = GlyphCache::new(engine)?;
+        Ok(Self {
+            font,
+            line_metrics,
+            cache: RefCell::new(cache),
+        })
+    }
+
+    pub fn load(engine: &mut Engine, path: impl AsRef<Path>) -> GameResult<Self> {
+        let bytes = engine.filesystem().read(path)?;
+        Self::from_bytes(engine, &bytes)
+    }
+
+    pub fn line_metrics(&self, size: f32) -> LineMetrics {
+        match self.line_metrics {
+            Some(metrics) => LineMetrics {
+                ascent: metrics.ascent * size,
+                descent: metrics.descent * size,
+                line_gap: metrics.line_gap * size,
+            },
+            None => LineMetrics {
+                ascent: size,
+                descent: 0.0,
+                line_gap: 0.0,
+            },
+        }
+    }
+
+    pub fn advance_width(&self, character: char, size: f32) -> f32 {
+        self.glyph_metrics(character, size).advance_width
+    }
+
+    pub(crate) fn glyph_metrics(&self, character: char, size: f32) -> GlyphMetrics {
+        if let Some(glyph) = self.cache.borrow().glyph(character, size) {
+            return GlyphMetrics {
+                offset_x: glyph.offset_x,
+                offset_y: glyph.offset_y,
+                width: glyph.region.width as f32,
+                height: glyph.region.height as f32,
+                advance_width: glyph.advance_width,
+            };
+        }
+        let metrics = self.font.metrics(character, size);
+        GlyphMetrics {
+            offset_x: metrics.bounds.xmin,
+            offset_y: -metrics.bounds.ymin - metrics.height as f32,
+            width: metrics.width as f32,
+            height: metrics.height as f32,
+            advance_width: metrics.advance_width,
+        }
+    }
+
+    pub(crate) fn is_glyph_cached(&self, character: char, size: f32) -> bool {
+        self.cache.borrow().contains(character, size)
+    }
+
+    pub(crate) fn cache_glyph(&self, character: char, size: f32) -> GameResult<CachedGlyph> {
+        self.cache.borrow_mut().cache(&self.font, character, size)
+    }
+
+    pub(crate) fn cache_glyphs(&self, glyphs: &[(char, f32)]) -> GameResult {
+        let mut cache = self.cache.borrow_mut();
+        let cache_all = |cache: &mut GlyphCache| -> GameResult {
+            for (character, size) in glyphs {
+                cache.cache(&self.font, *character, *size)?;
+            }
+            Ok(())
+        };
+        if cache_all(&mut cache).is_err() {
+            // the atlas is full, evict every glyph and keep only the ones drawn now
+            cache.clear()?;
+            cache_all(&mut cache)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn cache_texture(&self) -> Rc<opengl::Texture> {
+        self.cache.borrow().texture().clone()
+    }
+
+    pub(crate) fn cache_texture_size(&self) -> Size<u32> {
+        self.cache.borrow().size()
+    }
+
+}
+
+fn find_table<'a>(bytes: &'a [u8], tag: &[u8; 4]) -> Option<&'a [u8]> {
+    let num_tables = u16::from_be_bytes([*bytes.get(4)?, *bytes.get(5)?]) as usize;
+    for index in 0..num_tables {
+        let record = bytes.get(12 + index * 16..12 + (index + 1) * 16)?;
+        if &record[0..4] == tag {
+            let offset = u32::from_be_bytes([record[8], record[9], record[10], record[11]]) as usize;
+            let length = u32::from_be_bytes([record[12], record[13], record[14], record[15]]) as usize;
+            return bytes.get(offset..offset.checked_add(length)?);
+        }
+    }
+    None
+}
+
+fn read_line_metrics(bytes: &[u8]) -> Option<LineMetrics> {
+    // fontdue only exposes the summed line height, so read ascent, descent and line gap from the hhea table
+    let head = find_table(bytes, b"head")?;
+    let units_per_em = u16::from_be_bytes([*head.get(18)?, *head.get(19)?]);
+    if units_per_em == 0 {
+        return None;
+    }
+    let hhea = find_table(bytes, b"hhea")?;
+    let read = |offset: usize| -> Option<f32> {
+        Some(i16::from_be_bytes([*hhea.get(offset)?, *hhea.get(offset + 1)?]) as f32 / units_per_em as f32)
+    };
+    Some(LineMetrics {
+        ascent: read(4)?,
+        descent: read(6)?,
+        line_gap: read(8)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn font_bytes(units_per_em: u16, ascent: i16, descent: i16, line_gap: i16) -> Vec<u8> {
+        let mut head = vec![0; 54];
+        head[18..20].copy_from_slice(&units_per_em.to_be_bytes());
+        let mut hhea = vec![0; 36];
+        hhea[4..6].copy_from_slice(&ascent.to_be_bytes());
+        hhea[6..8].copy_from_slice(&descent.to_be_bytes());
+        hhea[8..10].copy_from_slice(&line_gap.to_be_bytes());
+
+        let mut bytes = vec![0, 1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0];
+        let mut offset = 12 + 2 * 16;
+        for (tag, table) in &[(b"head", &head), (b"hhea", &hhea)] {
+            bytes.extend_from_slice(*tag);
+            bytes.extend_from_slice(&[0; 4]);
+            bytes.extend_from_slice(&(offset as u32).to_be_bytes());
+            bytes.extend_from_slice(&(table.len() as u32).to_be_bytes());
+            offset += table.len();
+        }
+        bytes.extend_from_slice(&head);
+        bytes.extend_from_slice(&hhea);
+        bytes
+    }
+
+    #[test]
+    fn line_metrics() {
+        let metrics = read_line_metrics(&font_bytes(1000, 800, -200, 100)).unwrap();
+        assert_eq!(metrics, LineMetrics { ascent: 0.8, descent: -0.2, line_gap: 0.1 });
+        assert!((metrics.line_height() - 1.1).abs() < 1e-6);
+
+        assert_eq!(read_line_metrics(&font_bytes(0, 800, -200, 100)), None);
+        let bytes = font_bytes(1000, 800, -200, 100);
+        assert_eq!(read_line_metrics(&bytes[..bytes.len() - 36]), None);
+        assert_eq!(read_line_metrics(&[]), None);
+    }
+
+}
@@ -0,0 +1,237 @@
+use super::{Texture, TextureHolder, Filter, FilterMode, opengl};
+use crate::error::{GameError, GameResult};
+use crate::math::{Size, Region};
+use crate::engine::Engine;
+use glow::HasContext;
+use std::rc::Rc;
+use std::collections::HashMap;
+
+const INIT_CACHE_SIZE: u32 = 256;
+const GLYPH_PADDING: u32 = 1;
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+struct GlyphKey {
+    character: char,
+    size_bits: u32,
+}
+
+impl GlyphKey {
+
+    fn new(character: char, size: f32) -> Self {
+        Self { character, size_bits: size.to_bits() }
+    }
+
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CachedGlyph {
+    pub region: Region<u32>,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub advance_width: f32,
+}
+
+struct AtlasPacker {
+    size: Size<u32>,
+    max_size: u32,
+    cursor_x: u32,
+    cursor_y: u32,
+    row_height: u32,
+}
+
+impl AtlasPacker {
+
+    fn new(size: u32, max_size: u32) -> Self {
+        let size = size.min(max_size);
+        Self {
+            size: Size::new(size, size),
+            max_size,
+            cursor_x: 0,
+            cursor_y: 0,
+            row_height: 0,
+        }
+    }
+
+    fn allocate(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        if self.cursor_x + width + GLYPH_PADDING > self.size.width {
+            self.cursor_x = 0;
+            self.cursor_y += self.row_height;
+            self.row_height = 0;
+        }
+        if self.cursor_x + width + GLYPH_PADDING <= self.size.width && self.cursor_y + height + GLYPH_PADDING <= self.size.height {
+            let position = (self.cursor_x, self.cursor_y);
+            self.cursor_x += width + GLYPH_PADDING;
+            self.row_height = self.row_height.max(height + GLYPH_PADDING);
+            Some(position)
+        } else {
+            None
+        }
+    }
+
+    fn reset(&mut self) {
+        self.cursor_x = 0;
+        self.cursor_y = 0;
+        self.row_height = 0;
+    }
+
+    fn grow(&mut self) -> Option<Size<u32>> {
+        if self.size.width * 2 > self.max_size || self.size.height * 2 > self.max_size {
+            return None;
+        }
+        self.size = Size::new(self.size.width * 2, self.size.height * 2);
+        Some(self.size)
+    }
+
+}
+
+pub struct GlyphCache {
+    texture: Texture,
+    pixels: Vec<u8>,
+    packer: AtlasPacker,
+    glyphs: HashMap<GlyphKey, CachedGlyph>,
+}
+
+impl GlyphCache {
+
+    pub fn new(engine: &mut Engine) -> GameResult<Self> {
+        let max_size = unsafe {
+            engine.graphics().gl().get_parameter_i32(glow::MAX_TEXTURE_SIZE)
+        };
+        let packer = AtlasPacker::new(INIT_CACHE_SIZE, max_size.max(1) as u32);
+        let size = packer.size;
+        let pixels = vec![0; (size.width * size.height * 4) as usize];
+        let mut texture = Texture::new(engine, size, Some(&pixels))?;
+        texture.set_filter(Filter::new(FilterMode::Linear, FilterMode::Linear, None));
+        Ok(Self {
+            texture,
+            pixels,
+            packer,
+            glyphs: HashMap::new(),
+        })
+    }
+
+    pub fn texture(&self) -> &Rc<opengl::Texture> {
+        self.texture.texture()
+    }
+
+    pub fn size(&self) -> Size<u32> {
+        self.packer.size
+    }
+
+    pub fn glyph(&self, character: char, size: f32) -> Option<CachedGlyph> {
+        self.glyphs.get(&GlyphKey::new(character, size)).copied()
+    }
+
+    pub fn contains(&self, character: char, size: f32) -> bool {
+        self.glyphs.contains_key(&GlyphKey::new(character, size))
+    }
+
+    pub fn cache(&mut self, font: &fontdue::Font, character: char, size: f32) -> GameResult<CachedGlyph> {
+        let key = GlyphKey::new(character, size);
+        if let Some(glyph) = self.glyphs.get(&key) {
+            return Ok(*glyph);
+        }
+
+        let (metrics, coverage) = font.rasterize(character, size);
+        let width = metrics.width as u32;
+        let height = metrics.height as u32;
+        let region = if width > 0 && height > 0 {
+            let position = self.allocate(width, height)?;
+            let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+            for alpha in coverage {
+                pixels.extend_from_slice(&[255, 255, 255, alpha]);
+            }
+            let region = Region::new(position.0, position.1, width, height);
+            self.write_pixels(region, &pixels);
+            self.texture.update_pixels(region, Some(&pixels))?;
+            region
+        } else {
+            Region::new(0, 0, 0, 0)
+        };
+
+        let glyph = CachedGlyph {
+            region,
+            offset_x: metrics.bounds.xmin,
+            offset_y: -metrics.bounds.ymin - height as f32,
+            advance_width: metrics.advance_width,
+        };
+        self.glyphs.insert(key, glyph);
+        Ok(glyph)
+    }
+
+    pub fn clear(&mut self) -> GameResult {
+        self.packer.reset();
+        self.glyphs.clear();
+        let size = self.packer.size;
+        self.pixels = vec![0; (size.width * size.height * 4) as usize];
+        self.texture.init_pixels(size, Some(&self.pixels))
+    }
+
+    fn allocate(&mut self, width: u32, height: u32) -> GameResult<(u32, u32)> {
+        loop {
+            if let Some(position) = self.packer.allocate(width, height) {
+                return Ok(position);
+            }
+            let old_size = self.packer.size;
+            if self.packer.grow().is_none() {
+                return Err(GameError::RuntimeError(format!("glyph cache exceeds the max texture size {}", self.packer.max_size).into()));
+            }
+            self.resize(old_size)?;
+        }
+    }
+
+    fn resize(&mut self, old_size: Size<u32>) -> GameResult {
+        let new_size = self.packer.size;
+        let mut new_pixels = vec![0; (new_size.width * new_size.height * 4) as usize];
+        let row_length = (old_size.width * 4) as usize;
+        for y in 0..old_size.height as usize {
+            let src_start = y * row_length;
+            let dst_start = y * (new_size.width * 4) as usize;
+            new_pixels[dst_start..dst_start + row_length].copy_from_slice(&self.pixels[src_start..src_start + row_length]);
+        }
+        self.texture.init_pixels(new_size, Some(&new_pixels))?;
+        self.pixels = new_pixels;
+        Ok(())
+    }
+
+    fn write_pixels(&mut self, region: Region<u32>, pixels: &[u8]) {
+        let row_length = (region.width * 4) as usize;
+        for y in 0..region.height as usize {
+            let src_start = y * row_length;
+            let dst_start = ((region.y as usize + y) * self.packer.size.width as usize + region.x as usize) * 4;
+            self.pixels[dst_start..dst_start + row_length].copy_from_slice(&pixels[src_start..src_start + row_length]);
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack() {
+        let mut packer = AtlasPacker::new(16, 64);
+        assert_eq!(packer.allocate(5, 4), Some((0, 0)));
+        assert_eq!(packer.allocate(5, 6), Some((6, 0)));
+        assert_eq!(packer.allocate(5, 2), Some((0, 7)));
+        assert_eq!(packer.allocate(20, 2), None);
+        assert_eq!(packer.grow(), Some(Size::new(32, 32)));
+        assert_eq!(packer.allocate(20, 2), Some((0, 10)));
+        packer.reset();
+        assert_eq!(packer.allocate(5, 4), Some((0, 0)));
+        assert_eq!(packer.size, Size::new(32, 32));
+    }
+
+    #[test]
+    fn max_size() {
+        let mut packer = AtlasPacker::new(16, 32);
+        assert_eq!(packer.grow(), Some(Size::new(32, 32)));
+        assert_eq!(packer.grow(), None);
+        assert_eq!(packer.allocate(40, 2), None);
+
+        let packer = AtlasPacker::new(256, 128);
+        assert_eq!(packer.size, Size::new(128, 128));
+    }
+
+}
@@ -1,8 +1,33 @@
 use crate::error::{GameError, GameResult};
-use crate::math::Size;
+use super::{Color, FilterMode};
+use crate::math::{Position, Size, Region};
 use crate::engine::Engine;
+use image::{DynamicImage, ImageOutputFormat, RgbaImage};
 use std::path::Path;
 
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg { quality: u8 },
+    Bmp,
+    Tga,
+}
+
+impl ImageFormat {
+
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "png" => Some(ImageFormat::Png),
+            "jpg" | "jpeg" => Some(ImageFormat::Jpeg { quality: 90 }),
+            "bmp" => Some(ImageFormat::Bmp),
+            "tga" => Some(ImageFormat::Tga),
+            _ => None,
+        }
+    }
+
+}
+
 #[derive(Clone)]
 pub struct Image {
     size: Size<u32>,
@@ -20,7 +45,7 @@ impl Image {
     pub fn from_bytes(bytes: &[u8]) -> GameResult<Self> {
         let image = image::load_from_memory(bytes)
             .map_err(|error| GameError::InitError(Box::new(error)))?
-            .into_rgba();
+            .into_rgba8();
         let size = Size::new(image.width(), image.height());
         let pixels = image.into_raw();
         Self::new(size, pixels)
@@ -31,6 +56,35 @@ impl Image {
         Self::from_bytes(&bytes)
     }
 
+    pub fn encode(&self, format: ImageFormat) -> GameResult<Vec<u8>> {
+        let image = RgbaImage::from_raw(self.size.width, self.size.height, self.pixels.clone())
+            .ok_or_else(|| GameError::RuntimeError("illegal pixels length".into()))?;
+        let image = DynamicImage::ImageRgba8(image);
+        let (image, format) = match format {
+            ImageFormat::Png => (image, ImageOutputFormat::Png),
+            // jpeg has no alpha channel
+            ImageFormat::Jpeg { quality } => (DynamicImage::ImageRgb8(image.to_rgb8()), ImageOutputFormat::Jpeg(quality)),
+            ImageFormat::Bmp => (image, ImageOutputFormat::Bmp),
+            ImageFormat::Tga => (image, ImageOutputFormat::Tga),
+        };
+        let mut bytes = Vec::new();
+        image.write_to(&mut bytes, format)
+            .map_err(|error| GameError::RuntimeError(Box::new(error)))?;
+        Ok(bytes)
+    }
+
+    pub fn save(&self, engine: &mut Engine, path: impl AsRef<Path>) -> GameResult {
+        let path = path.as_ref();
+        let format = ImageFormat::from_path(path)
+            .ok_or_else(|| GameError::NotSupportedError(format!("unsupported image format: {}", path.display()).into()))?;
+        self.save_with_format(engine, path, format)
+    }
+
+    pub fn save_with_format(&self, engine: &mut Engine, path: impl AsRef<Path>, format: ImageFormat) -> GameResult {
+        let bytes = self.encode(format)?;
+        engine.filesystem().write(path, bytes)
+    }
+
     pub fn size(&self) -> Size<u32> {
         self.size
     }
@@ -43,6 +97,153 @@ impl Image {
         self.pixels
     }
 
+    fn index(&self, x: u32, y: u32) -> usize {
+        assert!(x < self.size.width, "x must < width");
+        assert!(y < self.size.height, "y must < height");
+        ((y * self.size.width + x) * 4) as usize
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> Color {
+        let index = self.index(x, y);
+        let pixel = &self.pixels[index..index + 4];
+        Color::from_u8(pixel[0], pixel[1], pixel[2], pixel[3])
+    }
+
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: impl Into<Color>) {
+        let index = self.index(x, y);
+        let color: Color = color.into();
+        let (red, green, blue, alpha): (u8, u8, u8, u8) = color.into();
+        self.pixels[index..index + 4].copy_from_slice(&[red, green, blue, alpha]);
+    }
+
+    pub fn fill(&mut self, color: impl Into<Color>) {
+        let color: Color = color.into();
+        let (red, green, blue, alpha): (u8, u8, u8, u8) = color.into();
+        for pixel in self.pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[red, green, blue, alpha]);
+        }
+    }
+
+    pub fn sub_image(&self, region: impl Into<Region<u32>>) -> GameResult<Self> {
+        let region = region.into();
+        if region.right() > self.size.width || region.bottom() > self.size.height {
+            return Err(GameError::RuntimeError("region out of image bounds".into()));
+        }
+        let stride = self.size.width as usize * 4;
+        let row_len = region.width as usize * 4;
+        let mut pixels = Vec::with_capacity(row_len * region.height as usize);
+        for y in region.top()..region.bottom() {
+            let start = y as usize * stride + region.x as usize * 4;
+            pixels.extend_from_slice(&self.pixels[start..start + row_len]);
+        }
+        Self::new(region.size(), pixels)
+    }
+
+    pub fn blit(&mut self, source: &Image, position: impl Into<Position<i32>>) {
+        let position = position.into();
+        let left = position.x.max(0);
+        let top = position.y.max(0);
+        let right = (position.x + source.size.width as i32).min(self.size.width as i32);
+        let bottom = (position.y + source.size.height as i32).min(self.size.height as i32);
+        for y in top..bottom {
+            for x in left..right {
+                let source_index = source.index((x - position.x) as u32, (y - position.y) as u32);
+                let index = self.index(x as u32, y as u32);
+                let pixel = blend_pixel(&source.pixels[source_index..source_index + 4], &self.pixels[index..index + 4]);
+                self.pixels[index..index + 4].copy_from_slice(&pixel);
+            }
+        }
+    }
+
+    fn transform(&self, size: Size<u32>, map: impl Fn(u32, u32) -> (u32, u32)) -> Self {
+        let mut pixels = Vec::with_capacity(self.pixels.len());
+        for y in 0..size.height {
+            for x in 0..size.width {
+                let (source_x, source_y) = map(x, y);
+                let index = self.index(source_x, source_y);
+                pixels.extend_from_slice(&self.pixels[index..index + 4]);
+            }
+        }
+        Self { size, pixels }
+    }
+
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.size.width;
+        self.transform(self.size, |x, y| (width - x - 1, y))
+    }
+
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.size.height;
+        self.transform(self.size, |x, y| (x, height - y - 1))
+    }
+
+    pub fn rotate_90(&self) -> Self {
+        let height = self.size.height;
+        self.transform(Size::new(self.size.height, self.size.width), |x, y| (y, height - x - 1))
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        let Size { width, height } = self.size;
+        self.transform(self.size, |x, y| (width - x - 1, height - y - 1))
+    }
+
+    pub fn rotate_270(&self) -> Self {
+        let width = self.size.width;
+        self.transform(Size::new(self.size.height, self.size.width), |x, y| (width - y - 1, x))
+    }
+
+    pub fn resize(&self, size: impl Into<Size<u32>>, filter: FilterMode) -> Self {
+        let size = size.into();
+        assert!(size.width > 0 && size.height > 0, "size must > 0");
+        let scale_x = self.size.width as f32 / size.width as f32;
+        let scale_y = self.size.height as f32 / size.height as f32;
+        match filter {
+            FilterMode::Nearest => self.transform(size, |x, y| {
+                let source_x = ((x as f32 + 0.5) * scale_x) as u32;
+                let source_y = ((y as f32 + 0.5) * scale_y) as u32;
+                (source_x.min(self.size.width - 1), source_y.min(self.size.height - 1))
+            }),
+            FilterMode::Linear => {
+                let mut pixels = Vec::with_capacity((size.width * size.height * 4) as usize);
+                for y in 0..size.height {
+                    let source_y = ((y as f32 + 0.5) * scale_y - 0.5).max(0.0).min((self.size.height - 1) as f32);
+                    let y0 = source_y.floor() as u32;
+                    let y1 = (y0 + 1).min(self.size.height - 1);
+                    let ty = source_y - y0 as f32;
+                    for x in 0..size.width {
+                        let source_x = ((x as f32 + 0.5) * scale_x - 0.5).max(0.0).min((self.size.width - 1) as f32);
+                        let x0 = source_x.floor() as u32;
+                        let x1 = (x0 + 1).min(self.size.width - 1);
+                        let tx = source_x - x0 as f32;
+                        let (i00, i10, i01, i11) = (self.index(x0, y0), self.index(x1, y0), self.index(x0, y1), self.index(x1, y1));
+                        for channel in 0..4 {
+                            let top = self.pixels[i00 + channel] as f32 * (1.0 - tx) + self.pixels[i10 + channel] as f32 * tx;
+                            let bottom = self.pixels[i01 + channel] as f32 * (1.0 - tx) + self.pixels[i11 + channel] as f32 * tx;
+                            pixels.push((top * (1.0 - ty) + bottom * ty).round() as u8);
+                        }
+                    }
+                }
+                Self { size, pixels }
+            }
+        }
+    }
+
+}
+
+fn blend_pixel(source: &[u8], destination: &[u8]) -> [u8; 4] {
+    let source_alpha = source[3] as f32 / 255.0;
+    let destination_alpha = destination[3] as f32 / 255.0;
+    let alpha = source_alpha + destination_alpha * (1.0 - source_alpha);
+    if alpha <= 0.0 {
+        return [0, 0, 0, 0];
+    }
+    let mut pixel = [0; 4];
+    for (channel, (source, destination)) in pixel.iter_mut().zip(source.iter().zip(destination)).take(3) {
+        let color = *source as f32 * source_alpha + *destination as f32 * destination_alpha * (1.0 - source_alpha);
+        *channel = (color / alpha).round() as u8;
+    }
+    pixel[3] = (alpha * 255.0).round() as u8;
+    pixel
 }
 
 pub fn validate_pixels(size: Size<u32>, pixels: &[u8]) -> GameResult {
@@ -52,3 +253,130 @@ pub fn validate_pixels(size: Size<u32>, pixels: &[u8]) -> GameResult {
         Err(GameError::RuntimeError("illegal pixels length".into()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format() {
+        assert_eq!(ImageFormat::from_path("shot.png"), Some(ImageFormat::Png));
+        assert_eq!(ImageFormat::from_path("shot.JPG"), Some(ImageFormat::Jpeg { quality: 90 }));
+        assert_eq!(ImageFormat::from_path("shots/shot.tga"), Some(ImageFormat::Tga));
+        assert_eq!(ImageFormat::from_path("shot.gif"), None);
+        assert_eq!(ImageFormat::from_path("shot"), None);
+    }
+
+    #[test]
+    fn encode() {
+        let pixels = vec![
+            255, 0, 0, 255, 0, 255, 0, 128,
+            0, 0, 255, 255, 255, 255, 255, 0,
+        ];
+        let image = Image::new((2, 2), pixels.clone()).unwrap();
+        let bytes = image.encode(ImageFormat::Png).unwrap();
+        let decoded = Image::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.size(), image.size());
+        assert_eq!(decoded.pixels(), &pixels[..]);
+        // tga has no magic number, so `from_bytes` can not detect it
+        let bytes = image.encode(ImageFormat::Tga).unwrap();
+        assert!(Image::from_bytes(&bytes).is_err());
+        let decoded = image::load_from_memory_with_format(&bytes, image::ImageFormat::Tga).unwrap().into_rgba8();
+        assert_eq!(decoded.dimensions(), (2, 2));
+        assert_eq!(decoded.into_raw(), pixels);
+        let bytes = image.encode(ImageFormat::Bmp).unwrap();
+        assert_eq!(Image::from_bytes(&bytes).unwrap().size(), image.size());
+        let bytes = image.encode(ImageFormat::Jpeg { quality: 100 }).unwrap();
+        let decoded = Image::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.size(), image.size());
+        assert!(decoded.pixels().chunks(4).all(|pixel| pixel[3] == 255));
+    }
+
+    fn checker() -> Image {
+        // 3x2
+        // R G B
+        // W K Y
+        let mut image = Image::new((3, 2), vec![0; 3 * 2 * 4]).unwrap();
+        image.set_pixel(0, 0, Color::RED);
+        image.set_pixel(1, 0, Color::GREEN);
+        image.set_pixel(2, 0, Color::BLUE);
+        image.set_pixel(0, 1, Color::WHITE);
+        image.set_pixel(1, 1, Color::BLACK);
+        image.set_pixel(2, 1, Color::YELLOW);
+        image
+    }
+
+    #[test]
+    fn pixel() {
+        let mut image = checker();
+        assert_eq!(image.get_pixel(1, 0), Color::GREEN);
+        assert_eq!(image.get_pixel(2, 1), Color::YELLOW);
+        image.fill(Color::CYAN);
+        assert!(image.pixels().chunks(4).all(|pixel| pixel == [0, 255, 255, 255]));
+
+        let image = checker();
+        let sub_image = image.sub_image((1, 0, 2, 2)).unwrap();
+        assert_eq!(sub_image.size(), Size::new(2, 2));
+        assert_eq!(sub_image.get_pixel(0, 0), Color::GREEN);
+        assert_eq!(sub_image.get_pixel(1, 1), Color::YELLOW);
+        assert!(image.sub_image((2, 0, 2, 1)).is_err());
+    }
+
+    #[test]
+    fn blit() {
+        let mut image = Image::new((2, 2), vec![0; 2 * 2 * 4]).unwrap();
+        image.fill(Color::BLUE);
+        let mut source = Image::new((2, 2), vec![0; 2 * 2 * 4]).unwrap();
+        source.fill(Color::RED);
+        source.set_pixel(1, 1, Color::from_u8(255, 0, 0, 0));
+        image.blit(&source, (1, -1));
+        assert_eq!(image.get_pixel(0, 0), Color::BLUE);
+        assert_eq!(image.get_pixel(1, 0), Color::RED);
+        assert_eq!(image.get_pixel(0, 1), Color::BLUE);
+        assert_eq!(image.get_pixel(1, 1), Color::BLUE);
+
+        let mut image = Image::new((1, 1), vec![0, 0, 255, 255]).unwrap();
+        let source = Image::new((1, 1), vec![255, 0, 0, 128]).unwrap();
+        image.blit(&source, (0, 0));
+        assert_eq!(image.pixels(), &[128, 0, 127, 255]);
+    }
+
+    #[test]
+    fn transform() {
+        let image = checker();
+        let flipped = image.flip_horizontal();
+        assert_eq!(flipped.get_pixel(0, 0), Color::BLUE);
+        assert_eq!(flipped.get_pixel(2, 1), Color::WHITE);
+        let flipped = image.flip_vertical();
+        assert_eq!(flipped.get_pixel(0, 0), Color::WHITE);
+        assert_eq!(flipped.get_pixel(2, 1), Color::BLUE);
+
+        let rotated = image.rotate_90();
+        assert_eq!(rotated.size(), Size::new(2, 3));
+        assert_eq!(rotated.get_pixel(0, 0), Color::WHITE);
+        assert_eq!(rotated.get_pixel(1, 0), Color::RED);
+        assert_eq!(rotated.get_pixel(1, 2), Color::BLUE);
+        assert_eq!(image.rotate_180().get_pixel(0, 0), Color::YELLOW);
+        let rotated = image.rotate_270();
+        assert_eq!(rotated.get_pixel(0, 0), Color::BLUE);
+        assert_eq!(rotated.get_pixel(1, 2), Color::WHITE);
+        assert_eq!(rotated.rotate_90().pixels(), image.pixels());
+    }
+
+    #[test]
+    fn resize() {
+        let image = checker();
+        let resized = image.resize((6, 4), FilterMode::Nearest);
+        assert_eq!(resized.size(), Size::new(6, 4));
+        assert_eq!(resized.get_pixel(1, 1), Color::RED);
+        assert_eq!(resized.get_pixel(5, 3), Color::YELLOW);
+
+        let image = Image::new((2, 1), vec![0, 0, 0, 255, 255, 255, 255, 255]).unwrap();
+        let resized = image.resize((4, 1), FilterMode::Linear);
+        assert_eq!(resized.get_pixel(0, 0), Color::BLACK);
+        assert_eq!(resized.get_pixel(3, 0), Color::WHITE);
+        assert_eq!(resized.pixels()[4], 64);
+        assert_eq!(resized.pixels()[8], 191);
+    }
+
+}
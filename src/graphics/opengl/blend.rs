@@ -0,0 +1,157 @@
+use glow::{Context, HasContext};
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum BlendEquation {
+    Add,
+    Subtract,
+    ReverseSubtract,
+    Min,
+    Max,
+}
+
+impl BlendEquation {
+
+    pub(crate) fn to_flag(&self) -> u32 {
+        match self {
+            BlendEquation::Add => glow::FUNC_ADD,
+            BlendEquation::Subtract => glow::FUNC_SUBTRACT,
+            BlendEquation::ReverseSubtract => glow::FUNC_REVERSE_SUBTRACT,
+            BlendEquation::Min => glow::MIN,
+            BlendEquation::Max => glow::MAX,
+        }
+    }
+
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum BlendFactor {
+    Zero,
+    One,
+    SrcColor,
+    OneMinusSrcColor,
+    DstColor,
+    OneMinusDstColor,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    DstAlpha,
+    OneMinusDstAlpha,
+    SrcAlphaSaturate,
+}
+
+impl BlendFactor {
+
+    pub(crate) fn to_flag(&self) -> u32 {
+        match self {
+            BlendFactor::Zero => glow::ZERO,
+            BlendFactor::One => glow::ONE,
+            BlendFactor::SrcColor => glow::SRC_COLOR,
+            BlendFactor::OneMinusSrcColor => glow::ONE_MINUS_SRC_COLOR,
+            BlendFactor::DstColor => glow::DST_COLOR,
+            BlendFactor::OneMinusDstColor => glow::ONE_MINUS_DST_COLOR,
+            BlendFactor::SrcAlpha => glow::SRC_ALPHA,
+            BlendFactor::OneMinusSrcAlpha => glow::ONE_MINUS_SRC_ALPHA,
+            BlendFactor::DstAlpha => glow::DST_ALPHA,
+            BlendFactor::OneMinusDstAlpha => glow::ONE_MINUS_DST_ALPHA,
+            BlendFactor::SrcAlphaSaturate => glow::SRC_ALPHA_SATURATE,
+        }
+    }
+
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct BlendComponent {
+    pub equation: BlendEquation,
+    pub src: BlendFactor,
+    pub dst: BlendFactor,
+}
+
+impl BlendComponent {
+
+    pub fn new(equation: BlendEquation, src: BlendFactor, dst: BlendFactor) -> Self {
+        Self { equation, src, dst }
+    }
+
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum BlendMode {
+    Alpha,
+    PremultipliedAlpha,
+    Additive,
+    /// Multiplies the destination by the source color.
+    /// The source color must be premultiplied by its alpha, like in `PremultipliedAlpha`.
+    Multiply,
+    /// Brightens the destination by the inverted source color.
+    /// The source color must be premultiplied by its alpha, like in `PremultipliedAlpha`.
+    Screen,
+    Subtract,
+    Replace,
+    Custom {
+        color: BlendComponent,
+        alpha: BlendComponent,
+    },
+}
+
+impl BlendMode {
+
+    pub fn custom(equation: BlendEquation, src: BlendFactor, dst: BlendFactor) -> Self {
+        let component = BlendComponent::new(equation, src, dst);
+        BlendMode::Custom {
+            color: component,
+            alpha: component,
+        }
+    }
+
+    pub fn components(&self) -> (BlendComponent, BlendComponent) {
+        use BlendEquation::*;
+        use BlendFactor::*;
+        match *self {
+            BlendMode::Alpha => (
+                BlendComponent::new(Add, SrcAlpha, OneMinusSrcAlpha),
+                BlendComponent::new(Add, SrcAlpha, OneMinusSrcAlpha),
+            ),
+            BlendMode::PremultipliedAlpha => (
+                BlendComponent::new(Add, One, OneMinusSrcAlpha),
+                BlendComponent::new(Add, One, OneMinusSrcAlpha),
+            ),
+            BlendMode::Additive => (
+                BlendComponent::new(Add, SrcAlpha, One),
+                BlendComponent::new(Add, Zero, One),
+            ),
+            BlendMode::Multiply => (
+                BlendComponent::new(Add, DstColor, OneMinusSrcAlpha),
+                BlendComponent::new(Add, One, OneMinusSrcAlpha),
+            ),
+            BlendMode::Screen => (
+                BlendComponent::new(Add, One, OneMinusSrcColor),
+                BlendComponent::new(Add, One, OneMinusSrcAlpha),
+            ),
+            BlendMode::Subtract => (
+                BlendComponent::new(ReverseSubtract, SrcAlpha, One),
+                BlendComponent::new(Add, Zero, One),
+            ),
+            BlendMode::Replace => (
+                BlendComponent::new(Add, One, Zero),
+                BlendComponent::new(Add, One, Zero),
+            ),
+            BlendMode::Custom { color, alpha } => (color, alpha),
+        }
+    }
+
+    pub(crate) fn apply(&self, gl: &Context) {
+        let (color, alpha) = self.components();
+        unsafe {
+            gl.blend_equation_separate(color.equation.to_flag(), alpha.equation.to_flag());
+            gl.blend_func_separate(color.src.to_flag(), color.dst.to_flag(), alpha.src.to_flag(), alpha.dst.to_flag());
+        }
+    }
+
+}
+
+impl Default for BlendMode {
+
+    fn default() -> Self {
+        BlendMode::Alpha
+    }
+
+}
@@ -7,6 +7,7 @@ mod wrap;
 mod texture;
 mod attachment;
 mod framebuffer;
+mod blend;
 
 pub use program::{ProgramId, Program};
 pub use vertex_array::{VertexArrayId, VertexArray};
@@ -17,3 +18,4 @@ pub use wrap::{WrapMode, Wrap};
 pub use texture::{TextureId, Texture};
 pub use attachment::Attachment;
 pub use framebuffer::{FramebufferId, Framebuffer};
+pub use blend::{BlendEquation, BlendFactor, BlendComponent, BlendMode};
@@ -0,0 +1,149 @@
+use super::shape;
+use crate::math::Position;
+use glam::Vec2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SubPath {
+    pub points: Vec<Vec2>,
+    pub closed: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Path {
+    subpaths: Vec<SubPath>,
+}
+
+fn to_vec2(position: impl Into<Position>) -> Vec2 {
+    let position = position.into();
+    Vec2::new(position.x, position.y)
+}
+
+impl Path {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn subpaths(&self) -> &[SubPath] {
+        &self.subpaths
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.subpaths.is_empty()
+    }
+
+    pub fn current_point(&self) -> Option<Position> {
+        let subpath = self.subpaths.last()?;
+        // closing a subpath moves the current point back to its start
+        let point = if subpath.closed {
+            subpath.points.first()?
+        } else {
+            subpath.points.last()?
+        };
+        Some(Position::new(point.x(), point.y()))
+    }
+
+    fn current_subpath(&mut self, point: Vec2) -> &mut SubPath {
+        let start = match self.subpaths.last() {
+            Some(subpath) if !subpath.closed => None,
+            Some(subpath) => Some(subpath.points[0]),
+            None => Some(point),
+        };
+        if let Some(start) = start {
+            self.subpaths.push(SubPath {
+                points: vec![start],
+                closed: false,
+            });
+        }
+        self.subpaths.last_mut().expect("path has no subpath")
+    }
+
+    pub fn move_to(mut self, point: impl Into<Position>) -> Self {
+        let point = to_vec2(point);
+        match self.subpaths.last_mut() {
+            Some(subpath) if !subpath.closed && subpath.points.len() == 1 => subpath.points[0] = point,
+            _ => self.subpaths.push(SubPath {
+                points: vec![point],
+                closed: false,
+            }),
+        }
+        self
+    }
+
+    pub fn line_to(mut self, point: impl Into<Position>) -> Self {
+        let point = to_vec2(point);
+        self.current_subpath(point).points.push(point);
+        self
+    }
+
+    pub fn quad_to(mut self, control: impl Into<Position>, point: impl Into<Position>) -> Self {
+        let control = to_vec2(control);
+        let point = to_vec2(point);
+        let subpath = self.current_subpath(control);
+        let from = subpath.points[subpath.points.len() - 1];
+        subpath.points.extend(shape::quad_points(from, control, point));
+        self
+    }
+
+    pub fn cubic_to(mut self, control1: impl Into<Position>, control2: impl Into<Position>, point: impl Into<Position>) -> Self {
+        let control1 = to_vec2(control1);
+        let control2 = to_vec2(control2);
+        let point = to_vec2(point);
+        let subpath = self.current_subpath(control1);
+        let from = subpath.points[subpath.points.len() - 1];
+        subpath.points.extend(shape::cubic_points(from, control1, control2, point));
+        self
+    }
+
+    pub fn close(mut self) -> Self {
+        if let Some(subpath) = self.subpaths.last_mut() {
+            subpath.closed = true;
+        }
+        self
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build() {
+        let path = Path::new()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .line_to((10.0, 10.0))
+            .close()
+            .line_to((0.0, 10.0));
+        assert_eq!(path.subpaths().len(), 2);
+        assert!(path.subpaths()[0].closed);
+        assert_eq!(path.subpaths()[0].points.len(), 3);
+        assert_eq!(path.subpaths()[1].points, vec![Vec2::new(0.0, 0.0), Vec2::new(0.0, 10.0)]);
+        assert_eq!(path.current_point(), Some(Position::new(0.0, 10.0)));
+
+        let path = Path::new()
+            .move_to((0.0, 0.0))
+            .move_to((5.0, 5.0))
+            .line_to((6.0, 6.0));
+        assert_eq!(path.subpaths().len(), 1);
+        assert_eq!(path.subpaths()[0].points[0], Vec2::new(5.0, 5.0));
+
+        let path = Path::new().line_to((1.0, 1.0));
+        assert_eq!(path.subpaths()[0].points, vec![Vec2::new(1.0, 1.0), Vec2::new(1.0, 1.0)]);
+    }
+
+    #[test]
+    fn curves() {
+        let path = Path::new()
+            .move_to((0.0, 0.0))
+            .quad_to((50.0, 100.0), (100.0, 0.0))
+            .cubic_to((100.0, 100.0), (0.0, 100.0), (0.0, 0.0))
+            .close();
+        let points = &path.subpaths()[0].points;
+        assert!(points.len() > 3);
+        assert_eq!(points[points.len() - 1], Vec2::new(0.0, 0.0));
+        assert_eq!(path.current_point(), Some(Position::new(0.0, 0.0)));
+    }
+
+}
@@ -0,0 +1,666 @@
+use super::{LineJoin, LineCap, FillRule, ShapeDrawParams};
+use glam::Vec2;
+use std::f32::consts::PI;
+
+const EPSILON: f32 = 1e-5;
+const DEFAULT_LINE_WIDTH: f32 = 1.0;
+const DEFAULT_MITER_LIMIT: f32 = 10.0;
+const CURVE_TOLERANCE: f32 = 0.25;
+const MIN_SEGMENTS: u32 = 8;
+const MAX_SEGMENTS: u32 = 256;
+const MAX_CURVE_SEGMENTS: u32 = 256;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StrokeStyle {
+    pub line_width: f32,
+    pub line_join: LineJoin,
+    pub line_cap: LineCap,
+    pub miter_limit: f32,
+}
+
+impl StrokeStyle {
+
+    pub fn new(params: &ShapeDrawParams) -> Self {
+        Self {
+            line_width: params.line_width.unwrap_or(DEFAULT_LINE_WIDTH),
+            line_join: params.line_join.unwrap_or(LineJoin::Miter),
+            line_cap: params.line_cap.unwrap_or(LineCap::Butt),
+            miter_limit: params.miter_limit.unwrap_or(DEFAULT_MITER_LIMIT),
+        }
+    }
+
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ShapeMesh {
+    pub positions: Vec<Vec2>,
+    pub elements: Vec<u32>,
+}
+
+impl ShapeMesh {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    fn push_position(&mut self, position: Vec2) -> u32 {
+        self.positions.push(position);
+        self.positions.len() as u32 - 1
+    }
+
+    pub fn push_triangle(&mut self, a: Vec2, b: Vec2, c: Vec2) {
+        let a = self.push_position(a);
+        let b = self.push_position(b);
+        let c = self.push_position(c);
+        self.elements.extend_from_slice(&[a, b, c]);
+    }
+
+    pub fn push_quad(&mut self, a: Vec2, b: Vec2, c: Vec2, d: Vec2) {
+        let a = self.push_position(a);
+        let b = self.push_position(b);
+        let c = self.push_position(c);
+        let d = self.push_position(d);
+        self.elements.extend_from_slice(&[a, b, c, a, c, d]);
+    }
+
+    pub fn push_fan(&mut self, center: Vec2, points: &[Vec2]) {
+        if points.len() < 2 {
+            return;
+        }
+        let center = self.push_position(center);
+        let first = self.positions.len() as u32;
+        self.positions.extend_from_slice(points);
+        for i in 0..points.len() as u32 - 1 {
+            self.elements.extend_from_slice(&[center, first + i, first + i + 1]);
+        }
+    }
+
+}
+
+fn perp(vector: Vec2) -> Vec2 {
+    Vec2::new(-vector.y(), vector.x())
+}
+
+fn cross(a: Vec2, b: Vec2) -> f32 {
+    a.x() * b.y() - a.y() * b.x()
+}
+
+pub fn segments_for_radius(radius: f32) -> u32 {
+    if radius <= CURVE_TOLERANCE {
+        return MIN_SEGMENTS;
+    }
+    // keep the distance between the chord and the true curve under the tolerance
+    let step = 2.0 * (1.0 - CURVE_TOLERANCE / radius).acos();
+    let segments = (2.0 * PI / step).ceil() as u32;
+    segments.clamp(MIN_SEGMENTS, MAX_SEGMENTS)
+}
+
+pub fn arc_segments(full_segments: u32, sweep: f32) -> u32 {
+    let segments = full_segments as f32 * sweep.abs() / (2.0 * PI);
+    // ignore rounding noise so that a half circle does not get an extra segment
+    ((segments - 1e-3).ceil() as u32).max(1)
+}
+
+pub fn arc_points(center: Vec2, radius: Vec2, start: f32, sweep: f32, segments: u32) -> Vec<Vec2> {
+    let segments = segments.max(1);
+    (0..=segments)
+        .map(|i| {
+            let angle = start + sweep * i as f32 / segments as f32;
+            center + Vec2::new(angle.cos() * radius.x(), angle.sin() * radius.y())
+        })
+        .collect()
+}
+
+pub fn ellipse_points(center: Vec2, radius: Vec2, segments: u32) -> Vec<Vec2> {
+    let mut points = arc_points(center, radius, 0.0, 2.0 * PI, segments);
+    points.pop();
+    points
+}
+
+fn clean_points(points: &[Vec2], closed: bool) -> Vec<Vec2> {
+    let mut cleaned: Vec<Vec2> = Vec::with_capacity(points.len());
+    for &point in points {
+        if cleaned.last().map_or(true, |last| (point - *last).length() > EPSILON) {
+            cleaned.push(point);
+        }
+    }
+    if closed && cleaned.len() > 1 && (cleaned[0] - cleaned[cleaned.len() - 1]).length() <= EPSILON {
+        cleaned.pop();
+    }
+    cleaned
+}
+
+pub fn fill_convex(mesh: &mut ShapeMesh, points: &[Vec2]) {
+    let points = clean_points(points, true);
+    if points.len() < 3 {
+        return;
+    }
+    mesh.push_fan(points[0], &points[1..]);
+}
+
+pub fn stroke(mesh: &mut ShapeMesh, points: &[Vec2], closed: bool, style: &StrokeStyle) {
+    let points = clean_points(points, closed);
+    let half_width = style.line_width / 2.0;
+    if points.len() < 2 {
+        // a single point is drawn as a dot by square and round caps
+        if let Some(&point) = points.first() {
+            match style.line_cap {
+                LineCap::Butt => {}
+                LineCap::Square => {
+                    let x = Vec2::new(half_width, 0.0);
+                    let y = Vec2::new(0.0, half_width);
+                    mesh.push_quad(point - x - y, point + x - y, point + x + y, point - x + y);
+                }
+                LineCap::Round => {
+                    let segments = segments_for_radius(half_width);
+                    mesh.push_fan(point, &arc_points(point, Vec2::new(half_width, half_width), 0.0, 2.0 * PI, segments));
+                }
+            }
+        }
+        return;
+    }
+    let closed = closed && points.len() > 2;
+    let count = points.len();
+    let segment_count = if closed { count } else { count - 1 };
+
+    // trim the inner side of each join so that translucent strokes do not overlap themselves,
+    // very short segments and self crossing paths still overlap
+    let mut inner_corners = vec![None; count];
+    let joins = if closed { 0..count } else { 1..count - 1 };
+    for i in joins.clone() {
+        let previous = points[(i + count - 1) % count];
+        let next = points[(i + 1) % count];
+        inner_corners[i] = inner_corner(previous, points[i], next, half_width);
+    }
+
+    for i in 0..segment_count {
+        let j = (i + 1) % count;
+        let mut from = points[i];
+        let mut to = points[j];
+        let direction = (to - from).normalize();
+        if !closed && style.line_cap == LineCap::Square {
+            if i == 0 {
+                from -= direction * half_width;
+            }
+            if i == segment_count - 1 {
+                to += direction * half_width;
+            }
+        }
+        let normal = perp(direction) * half_width;
+        let corner = |index: usize, point: Vec2, sign: f32| match inner_corners[index] {
+            Some((side, inner)) if sign * side < 0.0 => inner,
+            _ => point + normal * sign,
+        };
+        mesh.push_quad(corner(i, from, 1.0), corner(j, to, 1.0), corner(j, to, -1.0), corner(i, from, -1.0));
+    }
+
+    for i in joins {
+        let previous = points[(i + count - 1) % count];
+        let next = points[(i + 1) % count];
+        let inner = inner_corners[i].map(|(_, inner)| inner);
+        push_join(mesh, previous, points[i], next, inner, style, half_width);
+    }
+
+    if !closed && style.line_cap == LineCap::Round {
+        push_round_cap(mesh, points[0], (points[0] - points[1]).normalize(), half_width);
+        push_round_cap(mesh, points[count - 1], (points[count - 1] - points[count - 2]).normalize(), half_width);
+    }
+}
+
+fn inner_corner(previous: Vec2, point: Vec2, next: Vec2, half_width: f32) -> Option<(f32, Vec2)> {
+    let incoming = (point - previous).normalize();
+    let outgoing = (next - point).normalize();
+    let turn = cross(incoming, outgoing);
+    if turn.abs() <= EPSILON {
+        return None;
+    }
+    let side = if turn > 0.0 { -1.0 } else { 1.0 };
+    let miter = (perp(incoming) + perp(outgoing)).normalize();
+    let cos_half_angle = miter.dot(perp(incoming));
+    if cos_half_angle <= EPSILON {
+        return None;
+    }
+    let inner = point - miter * (half_width / cos_half_angle) * side;
+    // both neighbouring segments may be trimmed, so each join only takes up to half of them
+    let trim = (point - inner).dot(incoming).abs();
+    let max_trim = (point - previous).length().min((next - point).length()) / 2.0;
+    if trim <= max_trim {
+        Some((side, inner))
+    } else {
+        None
+    }
+}
+
+fn push_join(mesh: &mut ShapeMesh, previous: Vec2, point: Vec2, next: Vec2, inner: Option<Vec2>, style: &StrokeStyle, half_width: f32) {
+    let incoming = (point - previous).normalize();
+    let outgoing = (next - point).normalize();
+    let turn = cross(incoming, outgoing);
+    if turn.abs() <= EPSILON {
+        if incoming.dot(outgoing) < 0.0 {
+            // the path reverses, so the join wraps around the end of the incoming segment
+            match style.line_join {
+                LineJoin::Round => push_round_cap(mesh, point, incoming, half_width),
+                LineJoin::Bevel | LineJoin::Miter => {
+                    let normal = perp(incoming) * half_width;
+                    mesh.push_triangle(point + normal, point + incoming * half_width, point - normal);
+                }
+            }
+        }
+        return;
+    }
+    // the join fills the gap on the outer side of the turn
+    let side = if turn > 0.0 { -1.0 } else { 1.0 };
+    let outer_incoming = point + perp(incoming) * half_width * side;
+    let outer_outgoing = point + perp(outgoing) * half_width * side;
+    if let Some(inner) = inner {
+        // and the area the trimmed segments leave around the point
+        mesh.push_triangle(inner, outer_incoming, point);
+        mesh.push_triangle(inner, point, outer_outgoing);
+    }
+    match style.line_join {
+        LineJoin::Bevel => mesh.push_triangle(point, outer_incoming, outer_outgoing),
+        LineJoin::Miter => {
+            let miter = (perp(incoming) + perp(outgoing)).normalize();
+            let cos_half_angle = miter.dot(perp(incoming));
+            if cos_half_angle > EPSILON && 1.0 / cos_half_angle <= style.miter_limit {
+                let tip = point + miter * (half_width / cos_half_angle) * side;
+                mesh.push_quad(point, outer_incoming, tip, outer_outgoing);
+            } else {
+                mesh.push_triangle(point, outer_incoming, outer_outgoing);
+            }
+        }
+        LineJoin::Round => {
+            let from = outer_incoming - point;
+            let to = outer_outgoing - point;
+            let start = from.y().atan2(from.x());
+            let sweep = cross(from, to).atan2(from.dot(to));
+            let segments = arc_segments(segments_for_radius(half_width), sweep);
+            let radius = Vec2::new(half_width, half_width);
+            mesh.push_fan(point, &arc_points(point, radius, start, sweep, segments));
+        }
+    }
+}
+
+fn push_round_cap(mesh: &mut ShapeMesh, point: Vec2, direction: Vec2, half_width: f32) {
+    let start = direction.y().atan2(direction.x()) - PI / 2.0;
+    let segments = arc_segments(segments_for_radius(half_width), PI);
+    let radius = Vec2::new(half_width, half_width);
+    mesh.push_fan(point, &arc_points(point, radius, start, PI, segments));
+}
+
+fn curve_segments(deviation: f32) -> u32 {
+    let segments = (deviation / CURVE_TOLERANCE).sqrt().ceil() as u32;
+    segments.clamp(1, MAX_CURVE_SEGMENTS)
+}
+
+pub fn quad_points(from: Vec2, control: Vec2, to: Vec2) -> Vec<Vec2> {
+    // the chord error of a uniform step h is bounded by |p0 - 2p1 + p2| * h^2 / 4
+    let segments = curve_segments((from - control * 2.0 + to).length() / 4.0);
+    (1..=segments)
+        .map(|i| {
+            let t = i as f32 / segments as f32;
+            let u = 1.0 - t;
+            from * (u * u) + control * (2.0 * u * t) + to * (t * t)
+        })
+        .collect()
+}
+
+pub fn cubic_points(from: Vec2, control1: Vec2, control2: Vec2, to: Vec2) -> Vec<Vec2> {
+    // the chord error of a uniform step h is bounded by 3/4 * max|p(i) - 2p(i+1) + p(i+2)| * h^2
+    let deviation = (from - control1 * 2.0 + control2).length()
+        .max((control1 - control2 * 2.0 + to).length());
+    let segments = curve_segments(deviation * 3.0 / 4.0);
+    (1..=segments)
+        .map(|i| {
+            let t = i as f32 / segments as f32;
+            let u = 1.0 - t;
+            from * (u * u * u) + control1 * (3.0 * u * u * t) + control2 * (3.0 * u * t * t) + to * (t * t * t)
+        })
+        .collect()
+}
+
+struct Edge {
+    from: Vec2,
+    to: Vec2,
+    winding: i32,
+}
+
+impl Edge {
+
+    fn x_at(&self, y: f32) -> f32 {
+        self.from.x() + (y - self.from.y()) * (self.to.x() - self.from.x()) / (self.to.y() - self.from.y())
+    }
+
+}
+
+fn intersection(a: &Edge, b: &Edge) -> Option<Vec2> {
+    let a_direction = a.to - a.from;
+    let b_direction = b.to - b.from;
+    let denominator = cross(a_direction, b_direction);
+    if denominator.abs() <= EPSILON {
+        return None;
+    }
+    let offset = b.from - a.from;
+    let t = cross(offset, b_direction) / denominator;
+    let u = cross(offset, a_direction) / denominator;
+    if t > 0.0 && t < 1.0 && u > 0.0 && u < 1.0 {
+        Some(a.from + a_direction * t)
+    } else {
+        None
+    }
+}
+
+pub fn fill_polygons(mesh: &mut ShapeMesh, polygons: &[&[Vec2]], fill_rule: FillRule) {
+    let mut edges = Vec::new();
+    for polygon in polygons {
+        let points = clean_points(polygon, true);
+        if points.len() < 3 {
+            continue;
+        }
+        for i in 0..points.len() {
+            let from = points[i];
+            let to = points[(i + 1) % points.len()];
+            if (to.y() - from.y()).abs() <= EPSILON {
+                continue;
+            }
+            if from.y() < to.y() {
+                edges.push(Edge { from, to, winding: 1 });
+            } else {
+                edges.push(Edge { from: to, to: from, winding: -1 });
+            }
+        }
+    }
+
+    // split the shape into horizontal slabs in which no two edges cross,
+    // then fill the spans between the sorted edges of each slab by the fill rule
+    let mut ys: Vec<f32> = edges.iter().flat_map(|edge| vec![edge.from.y(), edge.to.y()]).collect();
+    for (i, a) in edges.iter().enumerate() {
+        for b in &edges[i + 1..] {
+            if a.from.y() < b.to.y() && b.from.y() < a.to.y() {
+                if let Some(point) = intersection(a, b) {
+                    ys.push(point.y());
+                }
+            }
+        }
+    }
+    ys.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    ys.dedup_by(|a, b| (*a - *b).abs() <= EPSILON);
+
+    let mut active = Vec::new();
+    for slab in ys.windows(2) {
+        let (top, bottom) = (slab[0], slab[1]);
+        let middle = (top + bottom) / 2.0;
+        active.clear();
+        active.extend(edges.iter()
+            .filter(|edge| edge.from.y() < middle && edge.to.y() > middle)
+            .map(|edge| (edge.x_at(middle), edge)));
+        active.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let mut winding = 0;
+        for pair in active.windows(2) {
+            let (left, right) = (pair[0].1, pair[1].1);
+            winding += match fill_rule {
+                FillRule::NonZero => left.winding,
+                FillRule::EvenOdd => 1,
+            };
+            let inside = match fill_rule {
+                FillRule::NonZero => winding != 0,
+                FillRule::EvenOdd => winding % 2 != 0,
+            };
+            if inside {
+                mesh.push_quad(
+                    Vec2::new(left.x_at(top), top),
+                    Vec2::new(right.x_at(top), top),
+                    Vec2::new(right.x_at(bottom), bottom),
+                    Vec2::new(left.x_at(bottom), bottom),
+                );
+            }
+        }
+    }
+}
+
+pub fn dash(points: &[Vec2], closed: bool, pattern: &[f32], offset: f32) -> Vec<Vec<Vec2>> {
+    let mut dashes = Vec::new();
+    if points.is_empty() {
+        return dashes;
+    }
+    // an odd pattern is repeated to keep dashes and gaps alternating
+    let pattern: Vec<f32> = if pattern.len() % 2 == 1 {
+        pattern.iter().chain(pattern.iter()).copied().collect()
+    } else {
+        pattern.to_vec()
+    };
+    let total: f32 = pattern.iter().sum();
+    if total <= 0.0 {
+        return vec![points.to_vec()];
+    }
+
+    let mut index = 0;
+    let mut remaining = offset.rem_euclid(total);
+    while remaining > pattern[index] || (remaining > 0.0 && remaining >= pattern[index]) {
+        remaining -= pattern[index];
+        index = (index + 1) % pattern.len();
+    }
+    remaining = pattern[index] - remaining;
+
+    let mut current = if index % 2 == 0 { vec![points[0]] } else { Vec::new() };
+    let segment_count = if closed { points.len() } else { points.len() - 1 };
+    for i in 0..segment_count {
+        let from = points[i];
+        let to = points[(i + 1) % points.len()];
+        let length = (to - from).length();
+        if length <= EPSILON {
+            continue;
+        }
+        let direction = (to - from) / length;
+        let mut position = 0.0;
+        while length - position > remaining {
+            position += remaining;
+            current.push(from + direction * position);
+            if index % 2 == 0 {
+                dashes.push(std::mem::take(&mut current));
+            }
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+        remaining -= length - position;
+        if index % 2 == 0 {
+            current.push(to);
+        }
+    }
+    if index % 2 == 0 && current.len() > 1 {
+        dashes.push(current);
+    }
+    dashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style(line_join: LineJoin, line_cap: LineCap) -> StrokeStyle {
+        StrokeStyle {
+            line_width: 2.0,
+            line_join,
+            line_cap,
+            miter_limit: DEFAULT_MITER_LIMIT,
+        }
+    }
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!((a - b).length() < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn segments() {
+        assert_eq!(segments_for_radius(0.0), MIN_SEGMENTS);
+        assert_eq!(segments_for_radius(100000.0), MAX_SEGMENTS);
+        assert!(segments_for_radius(100.0) > segments_for_radius(10.0));
+        assert_eq!(arc_segments(32, PI), 16);
+        assert_eq!(arc_segments(32, 0.0), 1);
+
+        let points = ellipse_points(Vec2::new(10.0, 10.0), Vec2::new(4.0, 2.0), 4);
+        assert_eq!(points.len(), 4);
+        assert_near(points[0], Vec2::new(14.0, 10.0));
+        assert_near(points[1], Vec2::new(10.0, 12.0));
+    }
+
+    #[test]
+    fn fill() {
+        let mut mesh = ShapeMesh::new();
+        let square = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(0.0, 0.0),
+        ];
+        fill_convex(&mut mesh, &square);
+        assert_eq!(mesh.elements.len(), 2 * 3);
+
+        let mut mesh = ShapeMesh::new();
+        fill_convex(&mut mesh, &square[..2]);
+        assert!(mesh.is_empty());
+    }
+
+    #[test]
+    fn stroke_caps() {
+        let line = [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)];
+
+        let mut mesh = ShapeMesh::new();
+        stroke(&mut mesh, &line, false, &style(LineJoin::Miter, LineCap::Butt));
+        assert_eq!(mesh.elements.len(), 6);
+        assert_near(mesh.positions[0], Vec2::new(0.0, 1.0));
+        assert_near(mesh.positions[1], Vec2::new(10.0, 1.0));
+        assert_near(mesh.positions[2], Vec2::new(10.0, -1.0));
+        assert_near(mesh.positions[3], Vec2::new(0.0, -1.0));
+
+        let mut mesh = ShapeMesh::new();
+        stroke(&mut mesh, &line, false, &style(LineJoin::Miter, LineCap::Square));
+        assert_near(mesh.positions[0], Vec2::new(-1.0, 1.0));
+        assert_near(mesh.positions[1], Vec2::new(11.0, 1.0));
+
+        let mut mesh = ShapeMesh::new();
+        stroke(&mut mesh, &line, false, &style(LineJoin::Miter, LineCap::Round));
+        assert!(mesh.elements.len() > 6);
+        assert!(mesh.positions.iter().any(|position| (*position - Vec2::new(-1.0, 0.0)).length() < 1e-4));
+
+        let mut mesh = ShapeMesh::new();
+        stroke(&mut mesh, &line[..1], false, &style(LineJoin::Miter, LineCap::Butt));
+        assert!(mesh.is_empty());
+    }
+
+    #[test]
+    fn stroke_joins() {
+        let corner = [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)];
+
+        let mut mesh = ShapeMesh::new();
+        stroke(&mut mesh, &corner, false, &style(LineJoin::Miter, LineCap::Butt));
+        assert_eq!(mesh.elements.len(), 3 * 6 + 2 * 3);
+        assert!(mesh.positions.iter().any(|position| (*position - Vec2::new(11.0, -1.0)).length() < 1e-4));
+        assert!(mesh.positions.iter().any(|position| (*position - Vec2::new(9.0, 1.0)).length() < 1e-4));
+
+        let mut mesh = ShapeMesh::new();
+        stroke(&mut mesh, &corner, false, &StrokeStyle { miter_limit: 1.0, ..style(LineJoin::Miter, LineCap::Butt) });
+        assert_eq!(mesh.elements.len(), 2 * 6 + 3 + 2 * 3);
+
+        let mut mesh = ShapeMesh::new();
+        stroke(&mut mesh, &corner, false, &style(LineJoin::Bevel, LineCap::Butt));
+        assert_eq!(mesh.elements.len(), 2 * 6 + 3 + 2 * 3);
+        assert!((area(&mesh) - 39.5).abs() < 1e-3);
+
+        let mut mesh = ShapeMesh::new();
+        stroke(&mut mesh, &corner, true, &style(LineJoin::Bevel, LineCap::Butt));
+        assert_eq!(mesh.elements.len(), 3 * 6 + 3 * 3 + 3 * 2 * 3);
+
+        let reversal = [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(5.0, 0.0)];
+        let mut mesh = ShapeMesh::new();
+        stroke(&mut mesh, &reversal, false, &style(LineJoin::Round, LineCap::Butt));
+        assert!(mesh.positions.iter().any(|position| (*position - Vec2::new(11.0, 0.0)).length() < 1e-4));
+        let mut mesh = ShapeMesh::new();
+        stroke(&mut mesh, &reversal, false, &style(LineJoin::Bevel, LineCap::Butt));
+        assert_eq!(mesh.elements.len(), 2 * 6 + 3);
+    }
+
+    fn area(mesh: &ShapeMesh) -> f32 {
+        mesh.elements.chunks(3)
+            .map(|triangle| {
+                let a = mesh.positions[triangle[0] as usize];
+                let b = mesh.positions[triangle[1] as usize];
+                let c = mesh.positions[triangle[2] as usize];
+                cross(b - a, c - a).abs() / 2.0
+            })
+            .sum()
+    }
+
+    fn polygon(points: &[(f32, f32)]) -> Vec<Vec2> {
+        points.iter().map(|(x, y)| Vec2::new(*x, *y)).collect()
+    }
+
+    #[test]
+    fn fill_rule() {
+        let concave = polygon(&[(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (1.0, 2.0), (0.0, 2.0)]);
+        let mut mesh = ShapeMesh::new();
+        fill_polygons(&mut mesh, &[&concave[..]], FillRule::NonZero);
+        assert!((area(&mesh) - 3.0).abs() < 1e-4);
+
+        let bowtie = polygon(&[(0.0, 0.0), (2.0, 2.0), (2.0, 0.0), (0.0, 2.0)]);
+        let mut mesh = ShapeMesh::new();
+        fill_polygons(&mut mesh, &[&bowtie[..]], FillRule::EvenOdd);
+        assert!((area(&mesh) - 2.0).abs() < 1e-4);
+
+        let outer = polygon(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]);
+        let inner = polygon(&[(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0)]);
+        let polygons = [&outer[..], &inner[..]];
+        let mut mesh = ShapeMesh::new();
+        fill_polygons(&mut mesh, &polygons, FillRule::NonZero);
+        assert!((area(&mesh) - 16.0).abs() < 1e-4);
+        let mut mesh = ShapeMesh::new();
+        fill_polygons(&mut mesh, &polygons, FillRule::EvenOdd);
+        assert!((area(&mesh) - 12.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn curves() {
+        let from = Vec2::new(0.0, 0.0);
+        let to = Vec2::new(100.0, 0.0);
+        let points = quad_points(from, Vec2::new(50.0, 100.0), to);
+        assert!(points.len() > 1);
+        assert_near(points[points.len() - 1], to);
+        assert!(points.iter().any(|point| (point.y() - 50.0).abs() < 1.0));
+
+        let points = quad_points(from, Vec2::new(50.0, 0.0), to);
+        assert_eq!(points.len(), 1);
+
+        let points = cubic_points(from, Vec2::new(0.0, 100.0), Vec2::new(100.0, 100.0), to);
+        assert!(points.len() > 1);
+        assert_near(points[points.len() - 1], to);
+    }
+
+    #[test]
+    fn dashes() {
+        let line = [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)];
+        let dashes = dash(&line, false, &[2.0, 3.0], 0.0);
+        assert_eq!(dashes.len(), 2);
+        assert_near(dashes[0][0], Vec2::new(0.0, 0.0));
+        assert_near(dashes[0][1], Vec2::new(2.0, 0.0));
+        assert_near(dashes[1][0], Vec2::new(5.0, 0.0));
+        assert_near(dashes[1][1], Vec2::new(7.0, 0.0));
+
+        let dashes = dash(&line, false, &[2.0, 3.0], 1.0);
+        assert_eq!(dashes.len(), 3);
+        assert_near(dashes[0][1], Vec2::new(1.0, 0.0));
+        assert_near(dashes[2][0], Vec2::new(9.0, 0.0));
+        assert_near(dashes[2][1], Vec2::new(10.0, 0.0));
+
+        let corner = [Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(2.0, 2.0)];
+        let dashes = dash(&corner, false, &[3.0], 0.0);
+        assert_eq!(dashes.len(), 1);
+        assert_eq!(dashes[0].len(), 3);
+        assert_near(dashes[0][2], Vec2::new(2.0, 1.0));
+    }
+
+}
@@ -0,0 +1,108 @@
+use super::Color;
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum DrawMode {
+    Fill,
+    Stroke,
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum LineJoin {
+    Miter,
+    Bevel,
+    Round,
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum LineCap {
+    Butt,
+    Square,
+    Round,
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum FillRule {
+    NonZero,
+    EvenOdd,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ShapeDrawParams {
+    pub mode: Option<DrawMode>,
+    pub line_width: Option<f32>,
+    pub line_join: Option<LineJoin>,
+    pub line_cap: Option<LineCap>,
+    pub miter_limit: Option<f32>,
+    pub segments: Option<u32>,
+    pub fill_rule: Option<FillRule>,
+    pub dash: Option<Vec<f32>>,
+    pub dash_offset: Option<f32>,
+    pub color: Option<Color>,
+}
+
+impl ShapeDrawParams {
+
+    pub fn mode(mut self, mode: DrawMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    pub fn fill() -> Self {
+        Self::default().mode(DrawMode::Fill)
+    }
+
+    pub fn stroke(line_width: f32) -> Self {
+        Self::default().mode(DrawMode::Stroke).line_width(line_width)
+    }
+
+    pub fn line_width(mut self, line_width: f32) -> Self {
+        assert!(line_width > 0.0, "line width must > 0.0");
+        self.line_width = Some(line_width);
+        self
+    }
+
+    pub fn line_join(mut self, line_join: LineJoin) -> Self {
+        self.line_join = Some(line_join);
+        self
+    }
+
+    pub fn line_cap(mut self, line_cap: LineCap) -> Self {
+        self.line_cap = Some(line_cap);
+        self
+    }
+
+    pub fn miter_limit(mut self, miter_limit: f32) -> Self {
+        assert!(miter_limit >= 1.0, "miter limit must >= 1.0");
+        self.miter_limit = Some(miter_limit);
+        self
+    }
+
+    pub fn segments(mut self, segments: u32) -> Self {
+        assert!(segments >= 3, "segments must >= 3");
+        self.segments = Some(segments);
+        self
+    }
+
+    pub fn fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = Some(fill_rule);
+        self
+    }
+
+    pub fn dash(mut self, dash: &[f32]) -> Self {
+        assert!(dash.iter().all(|length| *length >= 0.0), "dash length must >= 0.0");
+        assert!(dash.iter().sum::<f32>() > 0.0, "dash total length must > 0.0");
+        self.dash = Some(dash.to_vec());
+        self
+    }
+
+    pub fn dash_offset(mut self, dash_offset: f32) -> Self {
+        self.dash_offset = Some(dash_offset);
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+}
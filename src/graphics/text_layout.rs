@@ -0,0 +1,456 @@
+use super::{Color, Font, LineMetrics};
+use super::font::GlyphMetrics;
+use crate::math::Region;
+
+pub(crate) const DEFAULT_TEXT_SIZE: f32 = 14.0;
+
+pub(crate) trait FontMetrics {
+
+    fn line_metrics(&self, size: f32) -> LineMetrics;
+
+    fn glyph_metrics(&self, character: char, size: f32) -> GlyphMetrics;
+
+}
+
+impl FontMetrics for Font {
+
+    fn line_metrics(&self, size: f32) -> LineMetrics {
+        Font::line_metrics(self, size)
+    }
+
+    fn glyph_metrics(&self, character: char, size: f32) -> GlyphMetrics {
+        Font::glyph_metrics(self, character, size)
+    }
+
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+    Justify,
+}
+
+impl Default for TextAlign {
+
+    fn default() -> Self {
+        TextAlign::Left
+    }
+
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    pub size: Option<f32>,
+    pub color: Option<Color>,
+}
+
+impl TextSpan {
+
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            size: None,
+            color: None,
+        }
+    }
+
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+}
+
+impl From<&str> for TextSpan {
+
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+
+}
+
+impl From<String> for TextSpan {
+
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PositionedGlyph {
+    pub character: char,
+    pub size: f32,
+    pub color: Color,
+    pub region: Region,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct LayoutItem {
+    character: char,
+    size: f32,
+    color: Color,
+    metrics: GlyphMetrics,
+}
+
+impl LayoutItem {
+
+    fn is_whitespace(&self) -> bool {
+        self.character.is_whitespace()
+    }
+
+}
+
+struct LayoutLine {
+    items: Vec<LayoutItem>,
+    width: f32,
+    paragraph_end: bool,
+}
+
+impl LayoutLine {
+
+    fn new(mut items: Vec<LayoutItem>, paragraph_end: bool) -> Self {
+        while items.last().map(|item| item.is_whitespace()).unwrap_or(false) {
+            items.pop();
+        }
+        let width = items.iter().map(|item| item.metrics.advance_width).sum();
+        Self { items, width, paragraph_end }
+    }
+
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    spans: Vec<TextSpan>,
+    text_size: f32,
+    color: Color,
+    max_width: Option<f32>,
+    align: TextAlign,
+    line_spacing: f32,
+}
+
+impl TextLayout {
+
+    pub fn new() -> Self {
+        Self {
+            spans: Vec::new(),
+            text_size: DEFAULT_TEXT_SIZE,
+            color: Color::WHITE,
+            max_width: None,
+            align: TextAlign::default(),
+            line_spacing: 1.0,
+        }
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.spans.push(TextSpan::new(text));
+        self
+    }
+
+    pub fn span(mut self, span: impl Into<TextSpan>) -> Self {
+        self.spans.push(span.into());
+        self
+    }
+
+    pub fn text_size(mut self, text_size: f32) -> Self {
+        assert!(text_size > 0.0, "text size must > 0.0");
+        self.text_size = text_size;
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    pub fn max_width(mut self, max_width: Option<f32>) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn line_spacing(mut self, line_spacing: f32) -> Self {
+        assert!(line_spacing >= 0.0, "line spacing must >= 0.0");
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    pub fn spans(&self) -> &[TextSpan] {
+        &self.spans
+    }
+
+    pub fn layout(&self, font: &Font) -> (Vec<PositionedGlyph>, Region) {
+        self.layout_with(font)
+    }
+
+    pub fn measure(&self, font: &Font) -> Region {
+        self.layout_with(font).1
+    }
+
+    pub(crate) fn layout_with(&self, font: &impl FontMetrics) -> (Vec<PositionedGlyph>, Region) {
+        let items = self.collect_items(font);
+        let lines = self.break_lines(items);
+
+        let reference_width = match self.max_width {
+            Some(max_width) => max_width,
+            None => lines.iter().map(|line| line.width).fold(0.0, f32::max),
+        };
+
+        let mut glyphs = Vec::new();
+        let mut bounds: Option<(f32, f32)> = None;
+        let mut line_top = 0.0;
+        for line in &lines {
+            let (ascent, line_height) = self.line_vertical_metrics(font, line);
+            let extra_width = (reference_width - line.width).max(0.0);
+            let whitespace_count = line.items.iter().filter(|item| item.is_whitespace()).count();
+            let (offset_x, justify_space) = match self.align {
+                TextAlign::Left => (0.0, 0.0),
+                TextAlign::Center => (extra_width / 2.0, 0.0),
+                TextAlign::Right => (extra_width, 0.0),
+                TextAlign::Justify => {
+                    if line.paragraph_end || whitespace_count == 0 {
+                        (0.0, 0.0)
+                    } else {
+                        (0.0, extra_width / whitespace_count as f32)
+                    }
+                }
+            };
+            let line_width = if justify_space > 0.0 { reference_width } else { line.width };
+            bounds = Some(match bounds {
+                Some((left, right)) => (left.min(offset_x), right.max(offset_x + line_width)),
+                None => (offset_x, offset_x + line_width),
+            });
+
+            let baseline = line_top + ascent;
+            let mut pen_x = offset_x;
+            for item in &line.items {
+                if item.is_whitespace() {
+                    pen_x += item.metrics.advance_width + justify_space;
+                    continue;
+                }
+                if item.metrics.width > 0.0 && item.metrics.height > 0.0 {
+                    glyphs.push(PositionedGlyph {
+                        character: item.character,
+                        size: item.size,
+                        color: item.color,
+                        region: Region::new(
+                            pen_x + item.metrics.offset_x,
+                            baseline + item.metrics.offset_y,
+                            item.metrics.width,
+                            item.metrics.height,
+                        ),
+                    });
+                }
+                pen_x += item.metrics.advance_width;
+            }
+            line_top += line_height;
+        }
+
+        let (left, right) = bounds.unwrap_or((0.0, 0.0));
+        (glyphs, Region::new(left, 0.0, right - left, line_top))
+    }
+
+    fn collect_items(&self, font: &impl FontMetrics) -> Vec<LayoutItem> {
+        let mut items = Vec::new();
+        for span in &self.spans {
+            let size = span.size.unwrap_or(self.text_size);
+            let color = span.color.unwrap_or(self.color);
+            for character in span.text.chars() {
+                if character != '\n' && character.is_control() && character != '\t' {
+                    continue;
+                }
+                items.push(LayoutItem {
+                    character,
+                    size,
+                    color,
+                    metrics: font.glyph_metrics(character, size),
+                });
+            }
+        }
+        items
+    }
+
+    fn break_lines(&self, items: Vec<LayoutItem>) -> Vec<LayoutLine> {
+        let mut lines = Vec::new();
+        let mut current: Vec<LayoutItem> = Vec::new();
+        let mut current_width = 0.0;
+        let mut index = 0;
+        while index < items.len() {
+            if items[index].character == '\n' {
+                current.push(items[index]);
+                lines.push(LayoutLine::new(current.split_off(0), true));
+                current_width = 0.0;
+                index += 1;
+                continue;
+            }
+            let whitespace = items[index].is_whitespace();
+            let end = items[index..].iter()
+                .position(|item| item.character == '\n' || item.is_whitespace() != whitespace)
+                .map(|position| index + position)
+                .unwrap_or_else(|| items.len());
+            let token = &items[index..end];
+            let token_width: f32 = token.iter().map(|item| item.metrics.advance_width).sum();
+            match self.max_width {
+                Some(max_width) if !whitespace => {
+                    let has_content = current.iter().any(|item| !item.is_whitespace());
+                    if has_content && current_width + token_width > max_width {
+                        lines.push(LayoutLine::new(current.split_off(0), false));
+                        current_width = 0.0;
+                    }
+                    if token_width > max_width {
+                        for item in token {
+                            let has_content = current.iter().any(|item| !item.is_whitespace());
+                            if has_content && current_width + item.metrics.advance_width > max_width {
+                                lines.push(LayoutLine::new(current.split_off(0), false));
+                                current_width = 0.0;
+                            }
+                            current.push(*item);
+                            current_width += item.metrics.advance_width;
+                        }
+                    } else {
+                        current.extend_from_slice(token);
+                        current_width += token_width;
+                    }
+                }
+                _ => {
+                    current.extend_from_slice(token);
+                    current_width += token_width;
+                }
+            }
+            index = end;
+        }
+        lines.push(LayoutLine::new(current, true));
+        lines
+    }
+
+    fn line_vertical_metrics(&self, font: &impl FontMetrics, line: &LayoutLine) -> (f32, f32) {
+        let mut ascent: Option<f32> = None;
+        let mut descent: Option<f32> = None;
+        let mut line_gap: Option<f32> = None;
+        let mut visit = |metrics: LineMetrics| {
+            ascent = Some(ascent.map_or(metrics.ascent, |ascent| ascent.max(metrics.ascent)));
+            descent = Some(descent.map_or(metrics.descent, |descent| descent.min(metrics.descent)));
+            line_gap = Some(line_gap.map_or(metrics.line_gap, |line_gap| line_gap.max(metrics.line_gap)));
+        };
+        if line.items.is_empty() {
+            visit(font.line_metrics(self.text_size));
+        } else {
+            let mut last_size = None;
+            for item in &line.items {
+                if last_size != Some(item.size) {
+                    visit(font.line_metrics(item.size));
+                    last_size = Some(item.size);
+                }
+            }
+        }
+        let ascent = ascent.unwrap_or(0.0);
+        let descent = descent.unwrap_or(0.0);
+        let line_gap = line_gap.unwrap_or(0.0);
+        (ascent, (ascent - descent + line_gap) * self.line_spacing)
+    }
+
+}
+
+impl Default for TextLayout {
+
+    fn default() -> Self {
+        Self::new()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{FontMetrics, TextLayout, TextAlign, TextSpan};
+    use crate::graphics::{Color, LineMetrics};
+    use crate::graphics::font::GlyphMetrics;
+
+    struct MonospaceFont;
+
+    impl FontMetrics for MonospaceFont {
+
+        fn line_metrics(&self, size: f32) -> LineMetrics {
+            LineMetrics {
+                ascent: size * 0.8,
+                descent: -size * 0.2,
+                line_gap: 0.0,
+            }
+        }
+
+        fn glyph_metrics(&self, character: char, size: f32) -> GlyphMetrics {
+            let (width, height) = if character.is_whitespace() { (0.0, 0.0) } else { (size / 2.0, size / 2.0) };
+            GlyphMetrics {
+                offset_x: 0.0,
+                offset_y: -height,
+                width,
+                height,
+                advance_width: size / 2.0,
+            }
+        }
+
+    }
+
+    #[test]
+    fn measure() {
+        let (glyphs, bounds) = TextLayout::new().text_size(10.0).text("ab cd").layout_with(&MonospaceFont);
+        assert_eq!(glyphs.len(), 4);
+        assert_eq!(bounds.width, 25.0);
+        assert_eq!(bounds.height, 10.0);
+    }
+
+    #[test]
+    fn wrap() {
+        let layout = TextLayout::new()
+            .text_size(10.0)
+            .max_width(Some(30.0))
+            .text("aaa bbb cc\ndddddddd");
+        let (glyphs, bounds) = layout.layout_with(&MonospaceFont);
+        assert_eq!(glyphs.len(), 16);
+        assert_eq!(bounds.height, 10.0 * 4.0);
+        assert_eq!(bounds.width, 30.0);
+        assert_eq!(glyphs[3].region.x, 0.0);
+        assert_eq!(glyphs[3].region.y, 18.0 - 5.0);
+    }
+
+    #[test]
+    fn align() {
+        let layout = TextLayout::new()
+            .text_size(10.0)
+            .max_width(Some(100.0))
+            .text("ab cd");
+        let right = layout.clone().align(TextAlign::Right).layout_with(&MonospaceFont).0;
+        assert_eq!(right[0].region.x, 75.0);
+        let center = layout.clone().align(TextAlign::Center).layout_with(&MonospaceFont).0;
+        assert_eq!(center[0].region.x, 37.5);
+        let justify = layout.clone().max_width(Some(35.0)).align(TextAlign::Justify).text(" ef").layout_with(&MonospaceFont).0;
+        assert_eq!(justify[3].region.x, 30.0);
+    }
+
+    #[test]
+    fn spans() {
+        let layout = TextLayout::new()
+            .text_size(10.0)
+            .line_spacing(2.0)
+            .span(TextSpan::new("a").color(Color::RED))
+            .span(TextSpan::new("b").size(20.0));
+        let (glyphs, bounds) = layout.layout_with(&MonospaceFont);
+        assert_eq!(glyphs[0].color, Color::RED);
+        assert_eq!(glyphs[1].color, Color::WHITE);
+        assert_eq!(glyphs[1].region.x, 5.0);
+        assert_eq!(bounds.height, 40.0);
+    }
+
+}
@@ -0,0 +1,46 @@
+use super::Color;
+use crate::math::{Position, Point, Scale, Angle};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextDrawParams {
+    pub text_size: Option<f32>,
+    pub origin: Option<Point>,
+    pub position: Option<Position>,
+    pub rotation: Option<Angle>,
+    pub scale: Option<Scale>,
+    pub color: Option<Color>,
+}
+
+impl TextDrawParams {
+
+    pub fn text_size(mut self, text_size: f32) -> Self {
+        self.text_size = Some(text_size);
+        self
+    }
+
+    pub fn origin(mut self, origin: impl Into<Point>) -> Self {
+        self.origin = Some(origin.into());
+        self
+    }
+
+    pub fn position(mut self, position: impl Into<Position>) -> Self {
+        self.position = Some(position.into());
+        self
+    }
+
+    pub fn rotation(mut self, angle: Angle) -> Self {
+        self.rotation = Some(angle);
+        self
+    }
+
+    pub fn scale(mut self, scale: impl Into<Scale>) -> Self {
+        self.scale = Some(scale.into());
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+}
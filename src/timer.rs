@@ -1,10 +1,35 @@
+mod scheduler;
+mod frame_stats;
+
+pub use scheduler::{Scheduler, TaskHandle};
+pub use frame_stats::{FrameStats, FrameSample};
+
 use crate::error::GameResult;
 use std::time::{Instant, Duration};
 
+const MAX_FIXED_UPDATE_STEPS: u32 = 8;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum FramePacing {
+    Ready,
+    Spin,
+    WaitUntil(Instant),
+}
+
 pub struct Timer {
     fps: f32,
+    update_rate: Option<f32>,
+    spin_duration: Duration,
+    time_scale: f32,
+    paused: bool,
     last_instant: Instant,
     delta_time: Duration,
+    real_delta_time: Duration,
+    accumulator: Duration,
+    update_steps: u32,
+    step_delta_time: Option<Duration>,
+    scheduler: Scheduler,
+    stats: FrameStats,
 }
 
 impl Timer {
@@ -12,28 +37,124 @@ impl Timer {
     pub(crate) fn new(timer_config: TimerConfig) -> GameResult<Self> {
         Ok(Self {
             fps: timer_config.fps,
+            update_rate: timer_config.update_rate,
+            spin_duration: timer_config.spin_duration,
+            time_scale: 1.0,
+            paused: false,
             last_instant: Instant::now(),
             delta_time: Duration::new(0, 0),
+            real_delta_time: Duration::new(0, 0),
+            accumulator: Duration::new(0, 0),
+            update_steps: 0,
+            step_delta_time: None,
+            scheduler: Scheduler::new(),
+            stats: FrameStats::new(timer_config.stats_window),
         })
     }
 
     pub(crate) fn reset_tick(&mut self) {
         self.last_instant = Instant::now();
         self.delta_time = Duration::new(0, 0);
+        self.real_delta_time = Duration::new(0, 0);
+        self.accumulator = Duration::new(0, 0);
+        self.update_steps = 0;
+        self.step_delta_time = None;
+        self.stats.clear();
     }
 
     pub(crate) fn tick_and_check(&mut self) -> bool {
         let now_instant = Instant::now();
-        let delta_time = now_instant.duration_since(self.last_instant);
-        if delta_time.as_secs_f32() >= 1.0 / self.fps {
+        if self.frame_pacing(now_instant) == FramePacing::Ready {
+            let delta_time = now_instant.duration_since(self.last_instant);
             self.last_instant = now_instant;
-            self.delta_time = delta_time;
+            self.advance_frame(delta_time);
             true
         } else {
             false
         }
     }
 
+    pub(crate) fn step(&mut self, delta_time: Duration) {
+        self.last_instant = Instant::now();
+        self.advance_frame(delta_time);
+    }
+
+    fn frame_duration(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.fps as f64)
+    }
+
+    pub(crate) fn frame_pacing(&self, now_instant: Instant) -> FramePacing {
+        let next_instant = self.last_instant + self.frame_duration();
+        if now_instant >= next_instant {
+            FramePacing::Ready
+        } else if now_instant + self.spin_duration >= next_instant {
+            FramePacing::Spin
+        } else {
+            FramePacing::WaitUntil(next_instant - self.spin_duration)
+        }
+    }
+
+    fn advance_frame(&mut self, real_delta_time: Duration) {
+        self.real_delta_time = real_delta_time;
+        self.delta_time = if self.paused {
+            Duration::new(0, 0)
+        } else {
+            real_delta_time.mul_f64(self.time_scale as f64)
+        };
+        self.accumulator += self.delta_time;
+        self.scheduler.advance(self.delta_time);
+        self.update_steps = 0;
+        self.step_delta_time = None;
+    }
+
+    pub(crate) fn next_update(&mut self) -> bool {
+        self.step_delta_time = None;
+        match self.fixed_delta_time() {
+            Some(fixed_delta_time) => {
+                if self.paused || self.time_scale == 0.0 {
+                    // keep updating once per frame with a zero delta, so the game can still unpause itself
+                    return if self.update_steps == 0 {
+                        self.update_steps = 1;
+                        self.step_delta_time = Some(Duration::new(0, 0));
+                        true
+                    } else {
+                        false
+                    };
+                }
+                if self.accumulator < fixed_delta_time {
+                    return false;
+                }
+                if self.update_steps >= MAX_FIXED_UPDATE_STEPS {
+                    let remainder = self.accumulator.as_secs_f64() % fixed_delta_time.as_secs_f64();
+                    self.accumulator = Duration::from_secs_f64(remainder);
+                    return false;
+                }
+                self.accumulator -= fixed_delta_time;
+                self.update_steps += 1;
+                self.step_delta_time = Some(fixed_delta_time);
+                true
+            }
+            None => {
+                self.accumulator = Duration::new(0, 0);
+                if self.update_steps == 0 {
+                    self.update_steps = 1;
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    pub(crate) fn record_frame(&mut self, update_time: Duration, render_time: Duration, present_time: Duration) {
+        self.stats.record(FrameSample {
+            frame_time: self.real_delta_time,
+            update_time,
+            render_time,
+            present_time,
+        });
+    }
+
     pub fn fps(&self) -> f32 {
         self.fps
     }
@@ -43,8 +164,64 @@ impl Timer {
         self.fps = fps;
     }
 
+    pub fn spin_duration(&self) -> Duration {
+        self.spin_duration
+    }
+
+    pub fn set_spin_duration(&mut self, spin_duration: Duration) {
+        self.spin_duration = spin_duration;
+    }
+
+    pub fn update_rate(&self) -> Option<f32> {
+        self.update_rate
+    }
+
+    pub fn set_update_rate(&mut self, update_rate: Option<f32>) {
+        if let Some(update_rate) = update_rate {
+            assert_update_rate(update_rate);
+        }
+        self.update_rate = update_rate;
+        self.accumulator = Duration::new(0, 0);
+    }
+
+    pub fn is_fixed_update(&self) -> bool {
+        self.update_rate.is_some()
+    }
+
+    pub fn fixed_delta_time(&self) -> Option<Duration> {
+        self.update_rate.map(|update_rate| Duration::from_secs_f64(1.0 / update_rate as f64))
+    }
+
+    pub fn alpha(&self) -> f32 {
+        match self.fixed_delta_time() {
+            Some(fixed_delta_time) => (self.accumulator.as_secs_f32() / fixed_delta_time.as_secs_f32()).min(1.0),
+            None => 1.0,
+        }
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        assert!(time_scale >= 0.0, "time scale must >= 0.0");
+        self.time_scale = time_scale;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
     pub fn real_time_fps(&self) -> f32 {
-        let delta_time_secs_f32 = self.delta_time.as_secs_f32();
+        let delta_time_secs_f32 = self.real_delta_time.as_secs_f32();
         if delta_time_secs_f32 > 0.0 {
             1.0 / delta_time_secs_f32
         } else {
@@ -53,7 +230,20 @@ impl Timer {
     }
 
     pub fn delta_time(&self) -> Duration {
-        self.delta_time
+        // each fixed update step only advances the game by the fixed delta
+        self.step_delta_time.unwrap_or(self.delta_time)
+    }
+
+    pub fn real_delta_time(&self) -> Duration {
+        self.real_delta_time
+    }
+
+    pub fn scheduler(&mut self) -> &mut Scheduler {
+        &mut self.scheduler
+    }
+
+    pub fn stats(&self) -> &FrameStats {
+        &self.stats
     }
 
 }
@@ -61,12 +251,20 @@ impl Timer {
 #[derive(Debug, Clone)]
 pub struct TimerConfig {
     fps: f32,
+    update_rate: Option<f32>,
+    spin_duration: Duration,
+    stats_window: usize,
 }
 
 impl TimerConfig {
 
     pub fn new() -> Self {
-        Self { fps: 60.0 }
+        Self {
+            fps: 60.0,
+            update_rate: None,
+            spin_duration: Duration::new(0, 0),
+            stats_window: 120,
+        }
     }
 
     pub fn fps(mut self, fps: f32) -> Self {
@@ -75,8 +273,144 @@ impl TimerConfig {
         self
     }
 
+    pub fn spin_duration(mut self, spin_duration: Duration) -> Self {
+        self.spin_duration = spin_duration;
+        self
+    }
+
+    pub fn stats_window(mut self, stats_window: usize) -> Self {
+        assert!(stats_window > 0, "stats window must > 0");
+        self.stats_window = stats_window;
+        self
+    }
+
+    pub fn update_rate(mut self, update_rate: Option<f32>) -> Self {
+        if let Some(update_rate) = update_rate {
+            assert_update_rate(update_rate);
+        }
+        self.update_rate = update_rate;
+        self
+    }
+
 }
 
 fn assert_fps(fps: f32) {
     assert!(fps > 0.0, "fps must > 0.0");
 }
+
+fn assert_update_rate(update_rate: f32) {
+    assert!(update_rate > 0.0, "update rate must > 0.0");
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Timer, TimerConfig, FramePacing};
+    use std::time::{Instant, Duration};
+
+    fn count_updates(timer: &mut Timer) -> u32 {
+        let mut count = 0;
+        while timer.next_update() {
+            count += 1;
+        }
+        count
+    }
+
+    #[test]
+    fn frame_pacing() {
+        let mut timer = Timer::new(TimerConfig::new().fps(100.0).spin_duration(Duration::from_millis(2))).unwrap();
+        let last_instant = Instant::now();
+        timer.last_instant = last_instant;
+        assert_eq!(timer.frame_pacing(last_instant), FramePacing::WaitUntil(last_instant + Duration::from_millis(8)));
+        assert_eq!(timer.frame_pacing(last_instant + Duration::from_millis(9)), FramePacing::Spin);
+        assert_eq!(timer.frame_pacing(last_instant + Duration::from_millis(10)), FramePacing::Ready);
+
+        timer.set_spin_duration(Duration::new(0, 0));
+        assert_eq!(timer.frame_pacing(last_instant + Duration::from_millis(9)), FramePacing::WaitUntil(last_instant + Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn variable_update() {
+        let mut timer = Timer::new(TimerConfig::new()).unwrap();
+        timer.advance_frame(Duration::from_millis(16));
+        assert_eq!(count_updates(&mut timer), 1);
+        assert_eq!(timer.alpha(), 1.0);
+        timer.advance_frame(Duration::from_millis(40));
+        assert_eq!(count_updates(&mut timer), 1);
+    }
+
+    #[test]
+    fn fixed_update() {
+        let mut timer = Timer::new(TimerConfig::new().update_rate(Some(100.0))).unwrap();
+        assert_eq!(timer.fixed_delta_time(), Some(Duration::from_millis(10)));
+
+        timer.advance_frame(Duration::from_millis(25));
+        assert_eq!(count_updates(&mut timer), 2);
+        assert!((timer.alpha() - 0.5).abs() < 1e-3);
+        assert_eq!(timer.delta_time(), Duration::from_millis(25));
+
+        timer.advance_frame(Duration::from_millis(15));
+        let mut total_delta_time = Duration::new(0, 0);
+        while timer.next_update() {
+            assert_eq!(timer.delta_time(), Duration::from_millis(10));
+            total_delta_time += timer.delta_time();
+        }
+        assert_eq!(total_delta_time, Duration::from_millis(20));
+        assert_eq!(timer.delta_time(), Duration::from_millis(15));
+        assert_eq!(timer.alpha(), 0.0);
+
+        timer.advance_frame(Duration::from_millis(9));
+        assert_eq!(count_updates(&mut timer), 0);
+        assert!((timer.alpha() - 0.9).abs() < 1e-3);
+
+        timer.advance_frame(Duration::from_millis(1000));
+        assert_eq!(count_updates(&mut timer), 8);
+        assert!(timer.alpha() < 1.0);
+    }
+
+    #[test]
+    fn time_scale() {
+        let mut timer = Timer::new(TimerConfig::new().update_rate(Some(100.0))).unwrap();
+        timer.set_time_scale(0.5);
+        timer.advance_frame(Duration::from_millis(40));
+        assert_eq!(timer.delta_time(), Duration::from_millis(20));
+        assert_eq!(timer.real_delta_time(), Duration::from_millis(40));
+        assert_eq!(count_updates(&mut timer), 2);
+
+        timer.pause();
+        timer.advance_frame(Duration::from_millis(40));
+        assert_eq!(timer.delta_time(), Duration::new(0, 0));
+        assert_eq!(timer.real_delta_time(), Duration::from_millis(40));
+        assert!(timer.next_update());
+        assert_eq!(timer.delta_time(), Duration::new(0, 0));
+        assert!(!timer.next_update());
+
+        timer.resume();
+        timer.set_time_scale(0.0);
+        timer.advance_frame(Duration::from_millis(40));
+        assert_eq!(count_updates(&mut timer), 1);
+        timer.set_time_scale(1.0);
+        timer.pause();
+
+        timer.resume();
+        timer.set_time_scale(2.0);
+        timer.advance_frame(Duration::from_millis(10));
+        assert_eq!(timer.delta_time(), Duration::from_millis(20));
+        assert_eq!(count_updates(&mut timer), 2);
+    }
+
+    #[test]
+    fn scheduler() {
+        let mut timer = Timer::new(TimerConfig::new()).unwrap();
+        let task = timer.scheduler().every(Duration::from_millis(100));
+        timer.set_time_scale(0.5);
+        timer.advance_frame(Duration::from_millis(100));
+        assert_eq!(timer.scheduler().poll(task), 0);
+        timer.advance_frame(Duration::from_millis(100));
+        assert_eq!(timer.scheduler().poll(task), 1);
+        timer.pause();
+        timer.advance_frame(Duration::from_millis(400));
+        assert_eq!(timer.scheduler().poll(task), 0);
+    }
+
+}
@@ -0,0 +1,177 @@
+use std::time::Duration;
+use std::collections::VecDeque;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct FrameSample {
+    pub frame_time: Duration,
+    pub update_time: Duration,
+    pub render_time: Duration,
+    pub present_time: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct FrameStats {
+    window: usize,
+    samples: VecDeque<FrameSample>,
+}
+
+impl FrameStats {
+
+    pub(crate) fn new(window: usize) -> Self {
+        Self {
+            window,
+            samples: VecDeque::with_capacity(window),
+        }
+    }
+
+    pub(crate) fn record(&mut self, sample: FrameSample) {
+        if self.samples.len() >= self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = &FrameSample> {
+        self.samples.iter()
+    }
+
+    pub fn last(&self) -> Option<&FrameSample> {
+        self.samples.back()
+    }
+
+    fn average(&self, duration: impl Fn(&FrameSample) -> Duration) -> Duration {
+        if self.samples.is_empty() {
+            return Duration::new(0, 0);
+        }
+        let total = self.samples.iter().map(duration).sum::<Duration>();
+        total / self.samples.len() as u32
+    }
+
+    pub fn average_frame_time(&self) -> Duration {
+        self.average(|sample| sample.frame_time)
+    }
+
+    pub fn average_update_time(&self) -> Duration {
+        self.average(|sample| sample.update_time)
+    }
+
+    pub fn average_render_time(&self) -> Duration {
+        self.average(|sample| sample.render_time)
+    }
+
+    pub fn average_present_time(&self) -> Duration {
+        self.average(|sample| sample.present_time)
+    }
+
+    pub fn average_fps(&self) -> f32 {
+        let average_frame_time = self.average_frame_time().as_secs_f32();
+        if average_frame_time > 0.0 {
+            1.0 / average_frame_time
+        } else {
+            0.0
+        }
+    }
+
+    pub fn min_frame_time(&self) -> Duration {
+        self.samples.iter().map(|sample| sample.frame_time).min().unwrap_or_default()
+    }
+
+    pub fn max_frame_time(&self) -> Duration {
+        self.samples.iter().map(|sample| sample.frame_time).max().unwrap_or_default()
+    }
+
+    pub fn percentile_frame_time(&self, percentile: f32) -> Duration {
+        assert!((0.0..=100.0).contains(&percentile), "percentile must in 0.0 ~ 100.0");
+        if self.samples.is_empty() {
+            return Duration::new(0, 0);
+        }
+        let mut frame_times = self.samples.iter().map(|sample| sample.frame_time).collect::<Vec<_>>();
+        frame_times.sort();
+        let rank = (percentile / 100.0 * frame_times.len() as f32).ceil() as usize;
+        frame_times[rank.max(1).min(frame_times.len()) - 1]
+    }
+
+    pub fn p95_frame_time(&self) -> Duration {
+        self.percentile_frame_time(95.0)
+    }
+
+    pub fn p99_frame_time(&self) -> Duration {
+        self.percentile_frame_time(99.0)
+    }
+
+    pub fn histogram(&self, bucket_size: Duration, bucket_count: usize) -> Vec<usize> {
+        assert!(bucket_size > Duration::new(0, 0), "bucket size must > 0");
+        assert!(bucket_count > 0, "bucket count must > 0");
+        let mut buckets = vec![0; bucket_count];
+        for sample in &self.samples {
+            let index = (sample.frame_time.as_nanos() / bucket_size.as_nanos()) as usize;
+            buckets[index.min(bucket_count - 1)] += 1;
+        }
+        buckets
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{FrameStats, FrameSample};
+    use std::time::Duration;
+
+    fn frame(millis: u64) -> FrameSample {
+        FrameSample {
+            frame_time: Duration::from_millis(millis),
+            update_time: Duration::from_millis(millis / 4),
+            ..FrameSample::default()
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let mut stats = FrameStats::new(100);
+        assert_eq!(stats.average_fps(), 0.0);
+        assert_eq!(stats.p95_frame_time(), Duration::new(0, 0));
+        for millis in 1..=100 {
+            stats.record(frame(millis));
+        }
+        assert_eq!(stats.min_frame_time(), Duration::from_millis(1));
+        assert_eq!(stats.max_frame_time(), Duration::from_millis(100));
+        assert_eq!(stats.average_frame_time(), Duration::from_micros(50500));
+        assert_eq!(stats.p95_frame_time(), Duration::from_millis(95));
+        assert_eq!(stats.p99_frame_time(), Duration::from_millis(99));
+        assert_eq!(stats.percentile_frame_time(0.0), Duration::from_millis(1));
+        assert_eq!(stats.histogram(Duration::from_millis(50), 3), vec![49, 50, 1]);
+
+        stats.record(frame(200));
+        assert_eq!(stats.len(), 100);
+        assert_eq!(stats.min_frame_time(), Duration::from_millis(2));
+        assert_eq!(stats.last(), Some(&frame(200)));
+    }
+
+    #[test]
+    fn average_fps() {
+        let mut stats = FrameStats::new(4);
+        for _ in 0..8 {
+            stats.record(frame(20));
+        }
+        assert!((stats.average_fps() - 50.0).abs() < 1e-3);
+        assert_eq!(stats.average_update_time(), Duration::from_millis(5));
+    }
+
+}
@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct TaskHandle {
+    id: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Task {
+    handle: TaskHandle,
+    remaining: Duration,
+    interval: Option<Duration>,
+    fired: u32,
+    done: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+    next_id: u64,
+    tasks: Vec<Task>,
+}
+
+impl Scheduler {
+
+    pub(crate) fn new() -> Self {
+        Self {
+            next_id: 0,
+            tasks: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, remaining: Duration, interval: Option<Duration>) -> TaskHandle {
+        let handle = TaskHandle { id: self.next_id };
+        self.next_id += 1;
+        self.tasks.push(Task {
+            handle,
+            remaining,
+            interval,
+            fired: 0,
+            done: false,
+        });
+        handle
+    }
+
+    pub fn after(&mut self, delay: Duration) -> TaskHandle {
+        self.add(delay, None)
+    }
+
+    pub fn every(&mut self, interval: Duration) -> TaskHandle {
+        assert!(interval > Duration::new(0, 0), "interval must > 0");
+        self.add(interval, Some(interval))
+    }
+
+    fn task(&self, handle: TaskHandle) -> Option<&Task> {
+        self.tasks.iter().find(|task| task.handle == handle)
+    }
+
+    pub fn is_active(&self, handle: TaskHandle) -> bool {
+        self.task(handle).map(|task| !task.done).unwrap_or(false)
+    }
+
+    pub fn remaining(&self, handle: TaskHandle) -> Option<Duration> {
+        self.task(handle)
+            .filter(|task| !task.done)
+            .map(|task| task.remaining)
+    }
+
+    pub fn poll(&mut self, handle: TaskHandle) -> u32 {
+        match self.tasks.iter().position(|task| task.handle == handle) {
+            Some(index) => {
+                let task = &mut self.tasks[index];
+                let fired = task.fired;
+                task.fired = 0;
+                if task.done {
+                    self.tasks.remove(index);
+                }
+                fired
+            }
+            None => 0,
+        }
+    }
+
+    pub fn cancel(&mut self, handle: TaskHandle) -> bool {
+        let count = self.tasks.len();
+        self.tasks.retain(|task| task.handle != handle);
+        self.tasks.len() != count
+    }
+
+    pub fn clear(&mut self) {
+        self.tasks.clear();
+    }
+
+    pub(crate) fn advance(&mut self, delta_time: Duration) {
+        // finished tasks are kept until they are polled or cancelled, a frame may run no update to poll them
+        for task in self.tasks.iter_mut().filter(|task| !task.done) {
+            if delta_time < task.remaining {
+                task.remaining -= delta_time;
+                continue;
+            }
+            let overflow = delta_time - task.remaining;
+            task.fired = task.fired.saturating_add(1);
+            match task.interval {
+                Some(interval) => {
+                    let interval_nanos = interval.as_nanos();
+                    let catch_up = (overflow.as_nanos() / interval_nanos).min(u32::MAX as u128) as u32;
+                    task.fired = task.fired.saturating_add(catch_up);
+                    task.remaining = interval - Duration::from_nanos((overflow.as_nanos() % interval_nanos) as u64);
+                }
+                None => {
+                    task.remaining = Duration::new(0, 0);
+                    task.done = true;
+                }
+            }
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Scheduler;
+    use std::time::Duration;
+
+    #[test]
+    fn after() {
+        let mut scheduler = Scheduler::new();
+        let task = scheduler.after(Duration::from_millis(100));
+        scheduler.advance(Duration::from_millis(60));
+        assert_eq!(scheduler.poll(task), 0);
+        assert_eq!(scheduler.remaining(task), Some(Duration::from_millis(40)));
+        scheduler.advance(Duration::from_millis(60));
+        assert!(!scheduler.is_active(task));
+        assert_eq!(scheduler.poll(task), 1);
+        assert_eq!(scheduler.poll(task), 0);
+        assert!(!scheduler.cancel(task));
+
+        let unpolled = scheduler.after(Duration::from_millis(100));
+        scheduler.advance(Duration::from_millis(100));
+        scheduler.advance(Duration::from_millis(100));
+        assert_eq!(scheduler.poll(unpolled), 1);
+        assert!(scheduler.tasks.is_empty());
+
+        let cancelled = scheduler.after(Duration::from_millis(100));
+        scheduler.advance(Duration::from_millis(100));
+        assert!(scheduler.cancel(cancelled));
+        assert!(scheduler.tasks.is_empty());
+    }
+
+    #[test]
+    fn every() {
+        let mut scheduler = Scheduler::new();
+        let task = scheduler.every(Duration::from_millis(100));
+        scheduler.advance(Duration::from_millis(100));
+        assert_eq!(scheduler.poll(task), 1);
+        assert_eq!(scheduler.remaining(task), Some(Duration::from_millis(100)));
+        scheduler.advance(Duration::from_millis(350));
+        assert_eq!(scheduler.poll(task), 3);
+        assert_eq!(scheduler.remaining(task), Some(Duration::from_millis(50)));
+        assert!(scheduler.cancel(task));
+        assert!(!scheduler.is_active(task));
+        scheduler.advance(Duration::from_millis(100));
+        assert_eq!(scheduler.poll(task), 0);
+    }
+
+    #[test]
+    fn saturate() {
+        let mut scheduler = Scheduler::new();
+        let task = scheduler.every(Duration::from_nanos(1));
+        scheduler.advance(Duration::from_secs(5));
+        assert_eq!(scheduler.poll(task), u32::MAX);
+        scheduler.advance(Duration::from_secs(5));
+        scheduler.advance(Duration::from_secs(5));
+        assert_eq!(scheduler.poll(task), u32::MAX);
+    }
+
+}
@@ -1,20 +1,30 @@
 mod icon;
 mod dpi;
 mod fullscreen;
+mod context;
 
 pub use icon::Icon;
 pub use dpi::{LogicalPosition, PhysicalPosition, LogicalSize, PhysicalSize};
 pub use fullscreen::FullscreenMode;
+pub(crate) use context::WindowContext;
 
 use crate::error::{GameError, GameResult};
 use crate::filesystem::Filesystem;
+use crate::event::UserEvent;
 use winit::event_loop::EventLoop;
-use winit::window::WindowBuilder;
-use glutin::{ContextBuilder, ContextWrapper, PossiblyCurrent};
+use winit::window::{WindowBuilder, WindowId};
+use glutin::{ContextBuilder, NotCurrent, GlRequest, GlProfile, Api};
 use std::rc::Rc;
 
+// the default shaders are `#version 330 core`
+fn gl_context_builder<'a>() -> ContextBuilder<'a, NotCurrent> {
+    ContextBuilder::new()
+        .with_gl(GlRequest::Specific(Api::OpenGl, (3, 3)))
+        .with_gl_profile(GlProfile::Core)
+}
+
 pub struct Window {
-    context_wrapper: Rc<ContextWrapper<PossiblyCurrent, winit::window::Window>>,
+    context: Rc<WindowContext>,
     title: String,
     resizable: bool,
     maximized: bool,
@@ -27,10 +37,10 @@ pub struct Window {
 
 impl Window {
 
-    pub(crate) fn new(window_config: WindowConfig, event_loop: &EventLoop<()>, filesystem: &Filesystem) -> GameResult<Self> {
+    pub(crate) fn new(window_config: WindowConfig, event_loop: &EventLoop<UserEvent>, filesystem: &Filesystem) -> GameResult<Self> {
         let mut window_builder = WindowBuilder::new()
             .with_title(&window_config.title)
-            .with_window_icon(match window_config.icon {
+            .with_window_icon(match &window_config.icon {
                 Some(path) => {
                     let bytes = filesystem.read(path)?;
                     let icon = Icon::from_bytes(&bytes)?;
@@ -61,7 +71,7 @@ impl Window {
         if let Some(size) = window_config.max_inner_size {
             window_builder = window_builder.with_max_inner_size(winit::dpi::LogicalSize::new(size.width, size.height));
         }
-        let context_builder = ContextBuilder::new()
+        let context_builder = gl_context_builder()
             .with_vsync(window_config.vsync);
         let windowed_context = context_builder.build_windowed(window_builder, event_loop)
             .map_err(|error| GameError::InitError(Box::new(error)))?;
@@ -69,8 +79,19 @@ impl Window {
             windowed_context.make_current()
                 .map_err(|(_, error)| GameError::InitError(Box::new(error)))?
         };
-        Ok(Self {
-            context_wrapper: Rc::new(context_wrapper),
+        Ok(Self::from_context(WindowContext::Windowed(context_wrapper), window_config))
+    }
+
+    pub(crate) fn new_headless(window_config: WindowConfig) -> GameResult<Self> {
+        let size = window_config.inner_size.unwrap_or_else(|| LogicalSize::new(800.0, 600.0));
+        let context_builder = gl_context_builder();
+        let context = WindowContext::new_headless(context_builder, winit::dpi::PhysicalSize::new(size.width.round() as u32, size.height.round() as u32))?;
+        Ok(Self::from_context(context, window_config))
+    }
+
+    fn from_context(context: WindowContext, window_config: WindowConfig) -> Self {
+        Self {
+            context: Rc::new(context),
             title: window_config.title,
             resizable: window_config.resizable,
             maximized: window_config.maximized,
@@ -79,15 +100,33 @@ impl Window {
             always_on_top: window_config.always_on_top,
             visible: window_config.visible,
             focused: false,
-        })
+        }
     }
 
-    pub(crate) fn context_wrapper(&self) -> &Rc<ContextWrapper<PossiblyCurrent, winit::window::Window>> {
-        &self.context_wrapper
+    pub(crate) fn context(&self) -> &Rc<WindowContext> {
+        &self.context
     }
 
-    pub(crate) fn window(&self) -> &winit::window::Window {
-        self.context_wrapper.window()
+    pub(crate) fn window(&self) -> Option<&winit::window::Window> {
+        self.context.window()
+    }
+
+    fn headless_error() -> GameError {
+        GameError::NotSupportedError("not supported in headless mode".into())
+    }
+
+    pub(crate) fn id(&self) -> Option<WindowId> {
+        self.window().map(|window| window.id())
+    }
+
+    pub(crate) fn request_redraw(&self) {
+        if let Some(window) = self.window() {
+            window.request_redraw();
+        }
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.context.is_headless()
     }
 
     pub(crate) fn handle_focus_change_event(&mut self, focused: bool) {
@@ -100,94 +139,116 @@ impl Window {
 
     pub fn set_title(&mut self, title: impl Into<String>) {
         self.title = title.into();
-        self.window().set_title(&self.title);
+        if let Some(window) = self.window() {
+            window.set_title(&self.title);
+        }
     }
 
     pub fn set_icon(&mut self, icon: Option<Icon>) {
-        self.window().set_window_icon(icon.map(|icon| icon.into()));
+        if let Some(window) = self.window() {
+            window.set_window_icon(icon.map(|icon| icon.into()));
+        }
     }
 
     pub fn inner_size(&self) -> LogicalSize {
-        let physical_size = self.window().inner_size();
-        let scale_factor = self.window().scale_factor();
+        let physical_size = self.context.inner_size();
+        let scale_factor = self.context.scale_factor();
         let logical_size = physical_size.to_logical(scale_factor);
         LogicalSize::new(logical_size.width, logical_size.height)
     }
 
     pub fn set_inner_size(&mut self, size: impl Into<LogicalSize>) {
         let size = size.into();
-        self.window().set_inner_size(winit::dpi::LogicalSize::new(size.width, size.height));
+        if let Some(window) = self.window() {
+            window.set_inner_size(winit::dpi::LogicalSize::new(size.width, size.height));
+        }
     }
 
     pub fn outer_size(&self) -> LogicalSize {
-        let physical_size = self.window().outer_size();
-        let scale_factor = self.window().scale_factor();
+        let physical_size = self.window()
+            .map(|window| window.outer_size())
+            .unwrap_or_else(|| self.context.inner_size());
+        let scale_factor = self.context.scale_factor();
         let logical_size = physical_size.to_logical(scale_factor);
         LogicalSize::new(logical_size.width, logical_size.height)
     }
 
     pub fn set_min_inner_size(&mut self, size: Option<impl Into<LogicalSize>>) {
-        self.window().set_min_inner_size(size.map(|size| {
+        let size = size.map(|size| {
             let size = size.into();
             winit::dpi::LogicalSize::new(size.width, size.height)
-        }));
+        });
+        if let Some(window) = self.window() {
+            window.set_min_inner_size(size);
+        }
     }
 
     pub fn set_max_inner_size(&mut self, size: Option<impl Into<LogicalSize>>) {
-        self.window().set_max_inner_size(size.map(|size| {
+        let size = size.map(|size| {
             let size = size.into();
             winit::dpi::LogicalSize::new(size.width, size.height)
-        }));
+        });
+        if let Some(window) = self.window() {
+            window.set_max_inner_size(size);
+        }
     }
 
     pub fn inner_position(&self) -> GameResult<LogicalPosition> {
-        let physical_position = self.window().inner_position()
+        let window = self.window().ok_or_else(Self::headless_error)?;
+        let physical_position = window.inner_position()
             .map_err(|error| GameError::NotSupportedError(Box::new(error)))?;
-        let scale_factor = self.window().scale_factor();
+        let scale_factor = window.scale_factor();
         let logical_position = physical_position.to_logical(scale_factor);
         Ok(LogicalPosition::new(logical_position.x, logical_position.y))
     }
 
     pub fn outer_position(&self) -> GameResult<LogicalPosition> {
-        let physical_position = self.window().outer_position()
+        let window = self.window().ok_or_else(Self::headless_error)?;
+        let physical_position = window.outer_position()
             .map_err(|error| GameError::NotSupportedError(Box::new(error)))?;
-        let scale_factor = self.window().scale_factor();
+        let scale_factor = window.scale_factor();
         let logical_position = physical_position.to_logical(scale_factor);
         Ok(LogicalPosition::new(logical_position.x, logical_position.y))
     }
 
     pub fn set_outer_position(&mut self, position: impl Into<LogicalPosition>) {
         let position = position.into();
-        self.window().set_outer_position(winit::dpi::LogicalPosition::new(position.x, position.y));
+        if let Some(window) = self.window() {
+            window.set_outer_position(winit::dpi::LogicalPosition::new(position.x, position.y));
+        }
     }
 
     pub fn set_ime_position(&mut self, position: impl Into<LogicalPosition>) {
         let position = position.into();
-        self.window().set_ime_position(winit::dpi::LogicalPosition::new(position.x, position.y));
+        if let Some(window) = self.window() {
+            window.set_ime_position(winit::dpi::LogicalPosition::new(position.x, position.y));
+        }
     }
 
     pub fn scale_factor(&self) -> f32 {
-        self.window().scale_factor() as f32
+        self.context.scale_factor() as f32
     }
 
     pub fn fullscreen(&self) -> Option<FullscreenMode> {
-        self.window().fullscreen()
+        self.window()
+            .and_then(|window| window.fullscreen())
             .map(|fullscreen| FullscreenMode::from_raw(fullscreen))
     }
 
     pub fn is_fullscreen(&self) -> bool {
-        self.window().fullscreen().is_some()
+        self.fullscreen().is_some()
     }
 
     pub fn set_fullscreen(&mut self, fullscreen: Option<FullscreenMode>) -> GameResult {
+        let window = self.window().ok_or_else(Self::headless_error)?;
         let fullscreen = match fullscreen {
             Some(fullscreen_mode) => {
-                let monitor = self.window().current_monitor();
+                let monitor = window.current_monitor();
                 Some(fullscreen_mode.into_raw(monitor)?)
             }
             None => None,
         };
-        self.window().set_fullscreen(fullscreen);
+        window.set_fullscreen(fullscreen);
         Ok(())
     }
 
@@ -197,7 +258,9 @@ impl Window {
 
     pub fn set_resizable(&mut self, resizable: bool) {
         self.resizable = resizable;
-        self.window().set_resizable(self.resizable);
+        if let Some(window) = self.window() {
+            window.set_resizable(self.resizable);
+        }
     }
 
     pub fn is_maximized(&self) -> bool {
@@ -206,7 +269,9 @@ impl Window {
 
     pub fn set_maximized(&mut self, maximized: bool) {
         self.maximized = maximized;
-        self.window().set_maximized(self.maximized);
+        if let Some(window) = self.window() {
+            window.set_maximized(self.maximized);
+        }
     }
 
     pub fn is_transparent(&self) -> bool {
@@ -219,7 +284,9 @@ impl Window {
 
     pub fn set_decorations(&mut self, decorations: bool) {
         self.decorations = decorations;
-        self.window().set_decorations(self.decorations);
+        if let Some(window) = self.window() {
+            window.set_decorations(self.decorations);
+        }
     }
 
     pub fn is_always_on_top(&self) -> bool {
@@ -228,7 +295,9 @@ impl Window {
 
     pub fn set_always_on_top(&mut self, always_on_top: bool) {
         self.always_on_top = always_on_top;
-        self.window().set_always_on_top(self.always_on_top);
+        if let Some(window) = self.window() {
+            window.set_always_on_top(self.always_on_top);
+        }
     }
 
     pub fn is_visible(&self) -> bool {
@@ -237,7 +306,9 @@ impl Window {
 
     pub fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
-        self.window().set_visible(self.visible);
+        if let Some(window) = self.window() {
+            window.set_visible(self.visible);
+        }
     }
 
     pub fn is_focused(&self) -> bool {
@@ -0,0 +1,96 @@
+use crate::error::{GameError, GameResult};
+use winit::window::Window;
+use winit::dpi::PhysicalSize;
+use glutin::{ContextBuilder, ContextWrapper, Context, ContextError, NotCurrent, PossiblyCurrent};
+use std::ffi::c_void;
+
+pub(crate) enum WindowContext {
+    Windowed(ContextWrapper<PossiblyCurrent, Window>),
+    Headless {
+        context: Context<PossiblyCurrent>,
+        size: PhysicalSize<u32>,
+    },
+}
+
+impl WindowContext {
+
+    pub fn new_headless(context_builder: ContextBuilder<NotCurrent>, size: PhysicalSize<u32>) -> GameResult<Self> {
+        let context = build_headless_context(context_builder, size)?;
+        let context = unsafe {
+            context.make_current()
+                .map_err(|(_, error)| GameError::InitError(Box::new(error)))?
+        };
+        Ok(WindowContext::Headless { context, size })
+    }
+
+    pub fn window(&self) -> Option<&Window> {
+        match self {
+            WindowContext::Windowed(context_wrapper) => Some(context_wrapper.window()),
+            WindowContext::Headless { .. } => None,
+        }
+    }
+
+    pub fn is_headless(&self) -> bool {
+        match self {
+            WindowContext::Windowed(_) => false,
+            WindowContext::Headless { .. } => true,
+        }
+    }
+
+    pub fn get_proc_address(&self, symbol: &str) -> *const c_void {
+        match self {
+            WindowContext::Windowed(context_wrapper) => context_wrapper.get_proc_address(symbol),
+            WindowContext::Headless { context, .. } => context.get_proc_address(symbol),
+        }
+    }
+
+    pub fn inner_size(&self) -> PhysicalSize<u32> {
+        match self {
+            WindowContext::Windowed(context_wrapper) => context_wrapper.window().inner_size(),
+            WindowContext::Headless { size, .. } => *size,
+        }
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        match self {
+            WindowContext::Windowed(context_wrapper) => context_wrapper.window().scale_factor(),
+            WindowContext::Headless { .. } => 1.0,
+        }
+    }
+
+    pub fn resize(&self, physical_size: PhysicalSize<u32>) {
+        if let WindowContext::Windowed(context_wrapper) = self {
+            context_wrapper.resize(physical_size);
+        }
+    }
+
+    pub fn swap_buffers(&self) -> Result<(), ContextError> {
+        match self {
+            WindowContext::Windowed(context_wrapper) => context_wrapper.swap_buffers(),
+            WindowContext::Headless { .. } => Ok(()),
+        }
+    }
+
+}
+
+#[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"))]
+fn build_headless_context(context_builder: ContextBuilder<NotCurrent>, size: PhysicalSize<u32>) -> GameResult<Context<NotCurrent>> {
+    use glutin::platform::unix::HeadlessContextExt;
+    context_builder.build_osmesa(size)
+        .map_err(|error| GameError::InitError(Box::new(error)))
+}
+
+#[cfg(target_os = "windows")]
+fn build_headless_context(context_builder: ContextBuilder<NotCurrent>, size: PhysicalSize<u32>) -> GameResult<Context<NotCurrent>> {
+    use winit::platform::windows::EventLoopExtWindows;
+    // tests step headless engines off the main thread
+    let event_loop = winit::event_loop::EventLoop::<()>::new_any_thread();
+    context_builder.build_headless(&event_loop, size)
+        .map_err(|error| GameError::InitError(Box::new(error)))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd")))]
+fn build_headless_context(_: ContextBuilder<NotCurrent>, _: PhysicalSize<u32>) -> GameResult<Context<NotCurrent>> {
+    // an event loop can only be created on the main thread here, which headless engines can not rely on
+    Err(GameError::NotSupportedError("headless mode is not supported on this platform".into()))
+}
@@ -0,0 +1,208 @@
+use tge::error::{GameError, GameResult};
+use tge::engine::{Engine, EngineBuilder};
+use tge::window::WindowConfig;
+use tge::graphics::*;
+use tge::game::Game;
+use std::time::Duration;
+
+// these tests need an OSMesa (or pbuffer capable) OpenGL 3.3 implementation, run them with `cargo test -- --ignored`
+
+struct Counter {
+    started: bool,
+    updates: u32,
+    renders: u32,
+    messages: Vec<String>,
+    shutdown: bool,
+}
+
+impl Game for Counter {
+
+    fn on_start(&mut self, _: &mut Engine) -> GameResult {
+        self.started = true;
+        Ok(())
+    }
+
+    fn update(&mut self, _: &mut Engine) -> GameResult {
+        self.updates += 1;
+        Ok(())
+    }
+
+    fn render(&mut self, engine: &mut Engine) -> GameResult {
+        self.renders += 1;
+        engine.graphics().clear(Color::BLACK);
+        engine.graphics().draw_sprite(NO_TEXTURE, SpriteDrawParams::default().region((0.0, 0.0, 16.0, 16.0)));
+        Ok(())
+    }
+
+    fn event(&mut self, _: &mut Engine, event: tge::event::Event) -> GameResult<bool> {
+        if let tge::event::Event::User(event) = event {
+            if let Some(message) = event.downcast_ref::<String>() {
+                self.messages.push(message.clone());
+            }
+        }
+        Ok(false)
+    }
+
+    fn on_shutdown(&mut self, _: &mut Engine) -> GameResult {
+        self.shutdown = true;
+        Ok(())
+    }
+
+}
+
+#[test]
+#[ignore = "needs OSMesa / TGE_HEADLESS_GL"]
+fn step_frames() {
+    let mut engine = EngineBuilder::new()
+        .window_config(WindowConfig::new().inner_size((64.0, 64.0)))
+        .headless(true)
+        .build()
+        .unwrap();
+    assert!(engine.is_headless());
+    let mut game = Counter {
+        started: false,
+        updates: 0,
+        renders: 0,
+        messages: Vec::new(),
+        shutdown: false,
+    };
+
+    let proxy = engine.proxy();
+    std::thread::spawn(move || proxy.send_event(String::from("loaded")).unwrap()).join().unwrap();
+
+    for _ in 0..3 {
+        engine.step(&mut game, Duration::from_millis(16)).unwrap();
+    }
+    assert!(game.started);
+    assert_eq!(game.updates, 3);
+    assert_eq!(game.renders, 3);
+    assert_eq!(game.messages, vec!["loaded"]);
+    assert_eq!(engine.timer().delta_time(), Duration::from_millis(16));
+
+    engine.finish(&mut game).unwrap();
+    assert!(game.shutdown);
+    assert!(engine.step(&mut game, Duration::from_millis(16)).is_err());
+}
+
+struct Failing {
+    shutdown: bool,
+}
+
+impl Game for Failing {
+
+    fn update(&mut self, _: &mut Engine) -> GameResult {
+        Err(GameError::RuntimeError("update failed".into()))
+    }
+
+    fn render(&mut self, _: &mut Engine) -> GameResult {
+        Ok(())
+    }
+
+    fn on_shutdown(&mut self, _: &mut Engine) -> GameResult {
+        self.shutdown = true;
+        Ok(())
+    }
+
+}
+
+#[test]
+#[ignore = "needs OSMesa / TGE_HEADLESS_GL"]
+fn step_error() {
+    let mut engine = EngineBuilder::new()
+        .window_config(WindowConfig::new().inner_size((64.0, 64.0)))
+        .headless(true)
+        .build()
+        .unwrap();
+    let mut game = Failing { shutdown: false };
+    assert!(engine.step(&mut game, Duration::from_millis(16)).is_err());
+    assert!(game.shutdown);
+    assert!(engine.step(&mut game, Duration::from_millis(16)).is_err());
+    assert!(engine.finish(&mut game).is_err());
+}
+
+fn pixel(image: &Image, x: u32, y: u32) -> &[u8] {
+    let index = ((y * image.size().width + x) * 4) as usize;
+    &image.pixels()[index..index + 4]
+}
+
+#[test]
+#[ignore = "needs OSMesa / TGE_HEADLESS_GL"]
+fn read_pixels() {
+    let mut engine = EngineBuilder::new()
+        .window_config(WindowConfig::new().inner_size((64.0, 64.0)))
+        .headless(true)
+        .build()
+        .unwrap();
+    let mut game = Counter {
+        started: false,
+        updates: 0,
+        renders: 0,
+        messages: Vec::new(),
+        shutdown: false,
+    };
+    engine.step(&mut game, Duration::from_millis(16)).unwrap();
+    assert!(engine.graphics().screenshot().is_err());
+
+    engine.graphics().clear(Color::BLACK);
+    engine.graphics().draw_sprite(NO_TEXTURE, SpriteDrawParams::default().region((0.0, 0.0, 16.0, 16.0)));
+    let screenshot = engine.graphics().screenshot().unwrap();
+    assert_eq!(screenshot.size(), (64, 64).into());
+    assert_eq!(pixel(&screenshot, 0, 0), &[255, 255, 255, 255]);
+    assert_eq!(pixel(&screenshot, 63, 63), &[0, 0, 0, 255]);
+
+    let canvas = Canvas::new(&mut engine, (8, 8)).unwrap();
+    engine.graphics().set_canvas(Some(&canvas));
+    engine.graphics().clear(Color::RED);
+    engine.graphics().draw_sprite(NO_TEXTURE, SpriteDrawParams::default().region((0.0, 0.0, 4.0, 4.0)));
+    engine.graphics().set_canvas(NO_CANVAS);
+    let image = canvas.to_image(&mut engine).unwrap();
+    assert_eq!(image.size(), (8, 8).into());
+    assert_eq!(pixel(&image, 0, 0), &[255, 255, 255, 255]);
+    assert_eq!(pixel(&image, 7, 7), &[255, 0, 0, 255]);
+
+    engine.finish(&mut game).unwrap();
+}
+
+#[test]
+#[ignore = "needs OSMesa / TGE_HEADLESS_GL"]
+fn blend_modes() {
+    let mut engine = EngineBuilder::new()
+        .window_config(WindowConfig::new().inner_size((8.0, 8.0)))
+        .headless(true)
+        .build()
+        .unwrap();
+    let half_red = Color::new(1.0, 0.0, 0.0, 0.5);
+    let graphics = engine.graphics();
+    graphics.clear(Color::BLACK);
+    graphics.set_blend_mode(BlendMode::Additive);
+    graphics.draw_sprite(NO_TEXTURE, SpriteDrawParams::default().region((0.0, 0.0, 4.0, 8.0)).color(half_red));
+    graphics.set_blend_mode(BlendMode::Replace);
+    graphics.draw_sprite(NO_TEXTURE, SpriteDrawParams::default().position((4.0, 0.0)).region((0.0, 0.0, 4.0, 8.0)).color(half_red));
+    assert_eq!(graphics.blend_mode(), BlendMode::Replace);
+
+    let screenshot = graphics.screenshot().unwrap();
+    let additive = pixel(&screenshot, 0, 0);
+    assert!((127..=128).contains(&additive[0]));
+    assert_eq!(&additive[1..], &[0, 0, 255]);
+    let replace = pixel(&screenshot, 7, 7);
+    assert_eq!(&replace[..3], &[255, 0, 0]);
+    assert!((127..=128).contains(&replace[3]));
+
+    // half transparent premultiplied black only darkens the destination by half
+    let graphics = engine.graphics();
+    graphics.set_blend_mode(BlendMode::Replace);
+    graphics.draw_sprite(NO_TEXTURE, SpriteDrawParams::default().region((0.0, 0.0, 8.0, 8.0)).color(Color::WHITE));
+    graphics.set_blend_mode(BlendMode::Multiply);
+    graphics.draw_sprite(NO_TEXTURE, SpriteDrawParams::default().region((0.0, 0.0, 8.0, 8.0)).color(Color::new(0.0, 0.0, 0.0, 0.5)));
+    let screenshot = graphics.screenshot().unwrap();
+    let multiply = pixel(&screenshot, 0, 0);
+    assert!((127..=128).contains(&multiply[0]));
+
+    engine.finish(&mut Counter {
+        started: false,
+        updates: 0,
+        renders: 0,
+        messages: Vec::new(),
+        shutdown: false,
+    }).unwrap();
+}